@@ -0,0 +1,42 @@
+/*
+ * Copyright 2016 rs-transducers developers
+ *
+ * Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+ * http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+ * <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+ * option. This file may not be copied, modified, or distributed
+ * except according to those terms.
+ */
+extern crate rs_transducers;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rs_transducers::applications::vec::Into;
+use rs_transducers::transducers;
+
+const RUNS: u32 = 20;
+
+fn time<F>(name: &str, size: usize, f: F)
+    where F: Fn(Vec<u64>) -> Vec<u64> {
+
+    let mut total = Duration::new(0, 0);
+    for _ in 0..RUNS {
+        let source: Vec<u64> = (0..size as u64).collect();
+        let start = Instant::now();
+        black_box(f(source));
+        total += start.elapsed();
+    }
+    println!("{:<40} {:>10} values {:>10.3?} per run", name, size, total / RUNS);
+}
+
+fn main() {
+    for &size in &[1_000, 1_000_000] {
+        time("identity: transduce_into", size, |source| {
+            source.transduce_into::<(), _, _, _>(transducers::identity()).unwrap()
+        });
+        time("identity: transduce_into_or_move", size, |source| {
+            source.transduce_into_or_move::<(), _, _>(transducers::identity()).unwrap()
+        });
+    }
+}
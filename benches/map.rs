@@ -0,0 +1,51 @@
+/*
+ * Copyright 2016 rs-transducers developers
+ *
+ * Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+ * http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+ * <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+ * option. This file may not be copied, modified, or distributed
+ * except according to those terms.
+ */
+extern crate rs_transducers;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rs_transducers::applications::vec::Into;
+use rs_transducers::{compose, transducers};
+
+const SIZE: usize = 1_000_000;
+const RUNS: u32 = 20;
+
+fn time<F>(name: &str, mut f: F)
+    where F: FnMut() -> Vec<u64> {
+
+    black_box(f());
+    let mut total = Duration::new(0, 0);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(f());
+        total += start.elapsed();
+    }
+    println!("{:<36} {:>10.3?} per run", name, total / RUNS);
+}
+
+fn main() {
+    let source: Vec<u64> = (0..SIZE as u64).collect();
+
+    time("map: transduce_into", || {
+        source.clone().transduce_into::<(), _, _, _>(transducers::map(|x: u64| x * 3)).unwrap()
+    });
+    time("map: transduce_stateless_into", || {
+        source.clone().transduce_stateless_into(transducers::map(|x: u64| x * 3))
+    });
+    time("map . map: transduce_into", || {
+        let transducer = compose(transducers::map(|x: u64| x + 1), transducers::map(|x: u64| x * 3));
+        source.clone().transduce_into::<(), _, _, _>(transducer).unwrap()
+    });
+    time("map . map: transduce_stateless_into", || {
+        let transducer = compose(transducers::map(|x: u64| x + 1), transducers::map(|x: u64| x * 3));
+        source.clone().transduce_stateless_into(transducer)
+    });
+}
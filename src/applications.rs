@@ -7,210 +7,3010 @@
  * option. This file may not be copied, modified, or distributed
  * except according to those terms.
  */
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::cmp;
+use core::iter::{empty, Product, Sum};
+use core::marker::PhantomData;
+use core::ops::{Add, Mul};
+
+use ::{Transducer, Reducing, StepResult};
+
+/// Feeds each value of `source` through `reducing`, honouring early
+/// termination, and then completes the reducing function
+fn reduce<S, I, OF, E, R>(source: S, mut reducing: R) -> Result<(), E>
+    where S: IntoIterator<Item=I>,
+          R: Reducing<I, OF, E> {
+    reducing.init();
+    for val in source {
+        match reducing.step(val) {
+            Ok(StepResult::Continue) => (),
+            Ok(StepResult::Stop) => break,
+            Err(e) => return Err(e)
+        }
+    }
+    reducing.complete()
+}
+
+/// Recovers a value shared with a reducing function, once that function has
+/// been dropped
+fn unwrap_shared<T>(res: Rc<RefCell<T>>) -> T {
+    match Rc::try_unwrap(res) {
+        Ok(res) => res.into_inner(),
+        Err(_) => panic!("Other refs")
+    }
+}
+
+/// Applies `transducer` to `reducer`, then feeds it each value of `source`.
+/// Pass `&mut reducer` to retain access to the reducer afterwards.
+pub fn drive<S, T, R, RO, OF, E>(source: S, transducer: T, reducer: R) -> Result<(), E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, OF, E>,
+          T: Transducer<R, RO=RO> {
+    reduce(source, transducer.new(reducer))
+}
+
+/// Transduces `source`, of `len` values, into a new `Vec`.  `VE` is kept
+/// apart from `E` since `apply_to` and the other applications fix the error
+/// type of their `VecReducer` to `()`
+fn transduce_sized<S, X, T, O, RO, E, VE>(source: S,
+                                          len: usize,
+                                          transducer: T) -> Result<Vec<O>, E>
+    where S: IntoIterator<Item=X>,
+          RO: Reducing<X, Vec<O>, E>,
+          T: Transducer<vec::VecReducer<O, VE>, RO=RO> {
+    let res = Rc::new(RefCell::new(Vec::with_capacity(len)));
+    reduce(source, transducer.new(vec::VecReducer(res.clone(), PhantomData)))?;
+    Ok(unwrap_shared(res))
+}
+
+/// Transduces each value of `source` into a new `Vec`, without needing any
+/// application trait in scope
+pub fn apply_to<S, T, O, RO, E>(source: S, transducer: T) -> Result<Vec<O>, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, Vec<O>, E>,
+          T: Transducer<vec::VecReducer<O>, RO=RO> {
+    let source = source.into_iter();
+    let len = source.size_hint().0;
+    transduce_sized(source, len, transducer)
+}
+
+/// As `apply_to`, but into a custom terminal reducing function, see `drive`
+pub fn apply_to_with<S, T, R, RO, OF, E>(source: S, transducer: T, reducer: R) -> Result<(), E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, OF, E>,
+          T: Transducer<R, RO=RO> {
+    drive(source, transducer, reducer)
+}
+
+/// Drives `source` through `transducer` into a new `Vec`, the same as
+/// `apply_to`
+pub fn transduce<S, T, O, RO, E>(source: S, transducer: T) -> Result<Vec<O>, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, Vec<O>, E>,
+          T: Transducer<vec::VecReducer<O>, RO=RO> {
+    apply_to(source, transducer)
+}
+
+/// As `transduce`, but into a custom terminal reducing function, the same as
+/// `apply_to_with`
+pub fn transduce_with<S, T, R, RO, OF, E>(source: S, transducer: T, reducer: R) -> Result<(), E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, OF, E>,
+          T: Transducer<R, RO=RO> {
+    apply_to_with(source, transducer, reducer)
+}
+
+/// Folds each value into an accumulator, see `transduce_fold`
+pub struct FoldReducer<A, F> {
+    acc: Rc<RefCell<Option<A>>>,
+    f: F
+}
+
+impl<A, F, O> Reducing<O, A, ()> for FoldReducer<A, F>
+    where F: FnMut(A, O) -> A {
+
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, value: O) -> Result<StepResult, ()> {
+        let mut acc = self.acc.borrow_mut();
+        let next = (self.f)(acc.take().unwrap(), value);
+        *acc = Some(next);
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Drives `source` through `transducer`, folding each transduced value into
+/// an accumulator, starting from `init`, with `f`.  The equivalent of
+/// Clojure's `(transduce xf f init coll)`.
+///
+/// ```
+/// use rs_transducers::{transducers, transduce_fold};
+///
+/// let sum = transduce_fold(1..6, transducers::filter(|x: &u32| *x > 2), 0, |acc, x| acc + x);
+/// assert_eq!(Ok(12), sum);
+/// ```
+pub fn transduce_fold<S, T, A, F, RO, E>(source: S, transducer: T, init: A, f: F) -> Result<A, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, A, E>,
+          T: Transducer<FoldReducer<A, F>, RO=RO> {
+    let acc = Rc::new(RefCell::new(Some(init)));
+    reduce(source, transducer.new(FoldReducer {
+        acc: acc.clone(),
+        f
+    }))?;
+    Ok(unwrap_shared(acc).unwrap())
+}
+
+/// Sums the values `transducer` produces from `source`, zero if there are
+/// none
+pub fn transduce_sum<S, T, O, RO, E>(source: S, transducer: T) -> Result<O, E>
+    where O: Sum + Add<Output=O>,
+          S: IntoIterator,
+          RO: Reducing<S::Item, O, E>,
+          T: Transducer<FoldReducer<O, fn(O, O) -> O>, RO=RO> {
+    transduce_fold(source, transducer, empty().sum(), Add::add as fn(O, O) -> O)
+}
+
+/// Multiplies the values `transducer` produces from `source`, one if there
+/// are none
+pub fn transduce_product<S, T, O, RO, E>(source: S, transducer: T) -> Result<O, E>
+    where O: Product + Mul<Output=O>,
+          S: IntoIterator,
+          RO: Reducing<S::Item, O, E>,
+          T: Transducer<FoldReducer<O, fn(O, O) -> O>, RO=RO> {
+    transduce_fold(source, transducer, empty().product(), Mul::mul as fn(O, O) -> O)
+}
+
+/// Returns the smallest value `transducer` produces from `source`, the
+/// first of several equally small, or `None`
+pub fn transduce_min<S, T, O, RO, E>(source: S, transducer: T) -> Result<Option<O>, E>
+    where O: Ord,
+          S: IntoIterator,
+          RO: Reducing<S::Item, Option<O>, E>,
+          T: Transducer<FoldReducer<Option<O>, fn(Option<O>, O) -> Option<O>>, RO=RO> {
+    let f: fn(Option<O>, O) -> Option<O> = |min, x| Some(match min {
+        Some(min) => cmp::min(min, x),
+        None => x
+    });
+    transduce_fold(source, transducer, None, f)
+}
+
+/// Returns the largest value `transducer` produces from `source`, the last
+/// of several equally large, or `None`
+pub fn transduce_max<S, T, O, RO, E>(source: S, transducer: T) -> Result<Option<O>, E>
+    where O: Ord,
+          S: IntoIterator,
+          RO: Reducing<S::Item, Option<O>, E>,
+          T: Transducer<FoldReducer<Option<O>, fn(Option<O>, O) -> Option<O>>, RO=RO> {
+    let f: fn(Option<O>, O) -> Option<O> = |max, x| Some(match max {
+        Some(max) => cmp::max(max, x),
+        None => x
+    });
+    transduce_fold(source, transducer, None, f)
+}
+
+/// Keeps the first value and then stops, see `transduce_first`.  Values
+/// flushed into it on completion, after it has stopped, are ignored.
+pub struct FirstReducer<O>(Rc<RefCell<Option<O>>>);
+
+impl<O> Reducing<O, Option<O>, ()> for FirstReducer<O> {
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, value: O) -> Result<StepResult, ()> {
+        let mut first = self.0.borrow_mut();
+        if first.is_none() {
+            *first = Some(value);
+        }
+        Ok(StepResult::Stop)
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Keeps only the latest value, see `transduce_last`
+pub struct LastReducer<O>(Rc<RefCell<Option<O>>>);
+
+impl<O> Reducing<O, Option<O>, ()> for LastReducer<O> {
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, value: O) -> Result<StepResult, ()> {
+        *self.0.borrow_mut() = Some(value);
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Returns the first value `transducer` produces from `source`, or `None`.
+/// The source is abandoned as soon as a value is produced, so nothing after
+/// the value that produced it is read.
+pub fn transduce_first<S, T, O, RO, E>(source: S, transducer: T) -> Result<Option<O>, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, Option<O>, E>,
+          T: Transducer<FirstReducer<O>, RO=RO> {
+    let res = Rc::new(RefCell::new(None));
+    reduce(source, transducer.new(FirstReducer(res.clone())))?;
+    Ok(unwrap_shared(res))
+}
+
+/// Returns the last value `transducer` produces from `source`, including
+/// any flushed when it completes, or `None`
+pub fn transduce_last<S, T, O, RO, E>(source: S, transducer: T) -> Result<Option<O>, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, Option<O>, E>,
+          T: Transducer<LastReducer<O>, RO=RO> {
+    let res = Rc::new(RefCell::new(None));
+    reduce(source, transducer.new(LastReducer(res.clone())))?;
+    Ok(unwrap_shared(res))
+}
+
+/// Counts the values it is stepped with, see `transduce_count`
+pub struct CountReducer(Rc<RefCell<usize>>);
+
+impl<O> Reducing<O, usize, ()> for CountReducer {
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, _: O) -> Result<StepResult, ()> {
+        *self.0.borrow_mut() += 1;
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Returns the number of values `transducer` produces from `source`,
+/// without collecting them.  Each value counts, so a `mapcat` may produce
+/// several for one input.
+pub fn transduce_count<S, T, RO, E>(source: S, transducer: T) -> Result<usize, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, usize, E>,
+          T: Transducer<CountReducer, RO=RO> {
+    let res = Rc::new(RefCell::new(0));
+    reduce(source, transducer.new(CountReducer(res.clone())))?;
+    Ok(unwrap_shared(res))
+}
+
+/// Records whether any value satisfies a predicate, stopping at the first
+/// that does, see `transduce_any`
+pub struct AnyReducer<P> {
+    found: Rc<RefCell<bool>>,
+    pred: P
+}
+
+impl<P, O> Reducing<O, bool, ()> for AnyReducer<P>
+    where P: FnMut(&O) -> bool {
+
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, value: O) -> Result<StepResult, ()> {
+        if (self.pred)(&value) {
+            *self.found.borrow_mut() = true;
+            Ok(StepResult::Stop)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Records whether every value satisfies a predicate, stopping at the first
+/// that does not, see `transduce_all`
+pub struct AllReducer<P> {
+    all: Rc<RefCell<bool>>,
+    pred: P
+}
+
+impl<P, O> Reducing<O, bool, ()> for AllReducer<P>
+    where P: FnMut(&O) -> bool {
+
+    type Item = O;
+
+    #[inline]
+    fn step(&mut self, value: O) -> Result<StepResult, ()> {
+        if (self.pred)(&value) {
+            Ok(StepResult::Continue)
+        } else {
+            *self.all.borrow_mut() = false;
+            Ok(StepResult::Stop)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Returns whether any value `transducer` produces from `source` satisfies
+/// `pred`, abandoning the source at the first that does
+pub fn transduce_any<S, T, P, RO, E>(source: S, transducer: T, pred: P) -> Result<bool, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, bool, E>,
+          T: Transducer<AnyReducer<P>, RO=RO> {
+    let found = Rc::new(RefCell::new(false));
+    reduce(source, transducer.new(AnyReducer {
+        found: found.clone(),
+        pred
+    }))?;
+    Ok(unwrap_shared(found))
+}
+
+/// Returns whether every value `transducer` produces from `source`
+/// satisfies `pred`, abandoning the source at the first that does not.
+/// `true` if no values are produced.
+pub fn transduce_all<S, T, P, RO, E>(source: S, transducer: T, pred: P) -> Result<bool, E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, bool, E>,
+          T: Transducer<AllReducer<P>, RO=RO> {
+    let all = Rc::new(RefCell::new(true));
+    reduce(source, transducer.new(AllReducer {
+        all: all.clone(),
+        pred
+    }))?;
+    Ok(unwrap_shared(all))
+}
+
+struct TeeState<A, B> {
+    a: A,
+    b: B,
+    a_stopped: bool,
+    b_stopped: bool
+}
+
+/// Steps each value into two reducing functions, see `transduce_tee`
+pub struct TeeReducer<A, B>(Rc<RefCell<TeeState<A, B>>>);
+
+impl<I, A, B, OA, OB, E> Reducing<I, (OA, OB), E> for TeeReducer<A, B>
+    where I: Clone,
+          A: Reducing<I, OA, E>,
+          B: Reducing<I, OB, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        let mut state = self.0.borrow_mut();
+        state.a.init();
+        state.b.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let mut state = self.0.borrow_mut();
+        if !state.a_stopped {
+            if let StepResult::Stop = state.a.step(value.clone())? {
+                state.a_stopped = true;
+            }
+        }
+        if !state.b_stopped {
+            if let StepResult::Stop = state.b.step(value)? {
+                state.b_stopped = true;
+            }
+        }
+        if state.a_stopped && state.b_stopped {
+            Ok(StepResult::Stop)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        let mut state = self.0.borrow_mut();
+        state.a.complete()?;
+        state.b.complete()
+    }
+}
+
+/// Feeds each value of `source` through `transducer` and into both `a` and
+/// `b` in one pass, which are then returned.  Stops early only once both
+/// have stopped.
+pub fn transduce_tee<S, T, A, B, RO, OF, E>(source: S,
+                                            transducer: T,
+                                            a: A,
+                                            b: B) -> Result<(A, B), E>
+    where S: IntoIterator,
+          RO: Reducing<S::Item, OF, E>,
+          T: Transducer<TeeReducer<A, B>, RO=RO> {
+    let state = Rc::new(RefCell::new(TeeState {
+        a,
+        b,
+        a_stopped: false,
+        b_stopped: false
+    }));
+    reduce(source, transducer.new(TeeReducer(state.clone())))?;
+    let state = unwrap_shared(state);
+    Ok((state.a, state.b))
+}
+
+/// A reducing function built from a closure and an accumulator, see
+/// `fn_reducer`
+pub struct FnReducer<A, F> {
+    acc: A,
+    f: F
+}
+
+impl<A, F> FnReducer<A, F> {
+    pub fn finish(self) -> A {
+        self.acc
+    }
+}
+
+impl<A, F, I, E> Reducing<I, A, E> for FnReducer<A, F>
+    where F: FnMut(&mut A, I) -> Result<StepResult, E> {
+
+    type Item = I;
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        (self.f)(&mut self.acc, value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// Builds a reducing function that calls `f` with the accumulator for each
+/// value, the accumulator is returned by `finish`.
+///
+/// ```
+/// use rs_transducers::{transducers, StepResult};
+/// use rs_transducers::applications::{drive, fn_reducer};
+///
+/// let mut sum = fn_reducer(0, |acc, x| { *acc += x; Ok::<_, ()>(StepResult::Continue) });
+/// drive(vec![1, 2, 3], transducers::map(|x| x * 2), &mut sum).unwrap();
+/// assert_eq!(12, sum.finish());
+/// ```
+pub fn fn_reducer<A, F, I, E>(init: A, f: F) -> FnReducer<A, F>
+    where F: FnMut(&mut A, I) -> Result<StepResult, E> {
+
+    FnReducer {
+        acc: init,
+        f
+    }
+}
 
 pub mod vec {
-    use std::cell::RefCell;
-    use std::rc::Rc;
+    use alloc::collections::BinaryHeap;
+    use alloc::rc::Rc;
+    use alloc::string::{String, ToString};
+    use alloc::vec::{IntoIter, Vec};
+    use core::any::TypeId;
+    use core::cell::RefCell;
+    use core::cmp::Ordering;
+    use core::convert::{self, TryFrom};
+    use core::fmt::{Display, Write};
+    use core::marker::PhantomData;
+    use core::mem;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+    #[cfg(feature = "std")]
+    use std::hash::Hash;
+
+    use ::{Transducer, Reducing, Stateless, StepResult};
+    use ::transducers::{IdentityTransducer, StageStats, Stats};
+    use super::{reduce, transduce_sized, unwrap_shared};
+    use super::iter::{IterReducer, TransduceIter, TransduceIterator};
+
+    pub trait Ref {
+        type Input;
+
+        /// `E` is the error type of the transducer, `()` for those that
+        /// cannot fail, see `Into::transduce_into`
+        fn transduce_ref<'a, E, T, O, RO>(&'a self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<&'a Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+    }
+
+    /// Between `Ref` and `Into`, moves the values out of the vector but
+    /// leaves it in place, empty, so its allocation can be refilled
+    pub trait Drain {
+        type Input;
+
+        /// `E` is the error type of the transducer, see
+        /// `Into::transduce_into`.  The vector is left empty even on an
+        /// error.
+        fn transduce_drain<E, T, O, RO>(&mut self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+    }
+
+    /// Collects a stream of pairs into a pair of vectors in one pass
+    pub trait Unzip {
+        type Input;
+
+        fn transduce_unzip<T, A, B, RO, E>(self, transducer: T) -> Result<(Vec<A>, Vec<B>), E>
+            where RO: Reducing<Self::Input, (Vec<A>, Vec<B>), E>,
+                  T: Transducer<UnzipReducer<A, B, fn(A) -> A, fn(B) -> B>, RO=RO>;
+
+        /// As `transduce_unzip`, but with `f` applied to the first of each
+        /// pair and `g` to the second before they are collected
+        fn transduce_unzip_with<T, A, B, F, G, RO, E>(self,
+                                                      transducer: T,
+                                                      f: F,
+                                                      g: G) -> Result<(Vec<A>, Vec<B>), E>
+            where RO: Reducing<Self::Input, (Vec<A>, Vec<B>), E>,
+                  T: Transducer<UnzipReducer<A, B, F, G>, RO=RO>;
+    }
+
+    pub trait Into {
+        type Input;
+
+        /// `E` is the error type of the transducer, the first error
+        /// encountered is returned.  Where nothing else fixes it, e.g. for
+        /// transducers that cannot fail, it must be given, as in
+        /// `transduce_into::<(), _, _, _>`
+        fn transduce_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// The same as `transduce_into`
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// As `transduce_try_into`, but the values collected before an error
+        /// are returned along with it
+        fn transduce_collect_partial<E, T, O, RO>(self, transducer: T) -> (Vec<O>, Option<E>)
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// Collects a stream of `Result`s, the `Ok` values into one `Vec` and
+        /// the errors into another, without aborting on errors
+        fn transduce_partition_results<T, O, FE, RO, E>(self, transducer: T)
+                                                        -> Result<(Vec<O>, Vec<FE>), E>
+            where RO: Reducing<Self::Input, (Vec<O>, Vec<FE>), E>,
+                  T: Transducer<PartitionResultsReducer<O, FE>, RO=RO>;
+
+        /// As `transduce_into` for a transducer that keeps the value type.
+        /// When the transducer is `identity`, the vector is moved back as
+        /// it is without visiting any values
+        fn transduce_into_or_move<E, T, RO>(self, transducer: T) -> Result<Vec<Self::Input>, E>
+            where RO: Reducing<Self::Input, Vec<Self::Input>, E>,
+                  T: Transducer<VecReducer<Self::Input, E>, RO=RO> + 'static;
+
+        /// As `transduce_into`, for chains of `map` (and `identity`) only.
+        /// Such chains cannot fail, stop early or change the number of
+        /// values, so each one is applied directly in a tight loop
+        fn transduce_stateless_into<T, O>(self, transducer: T) -> Vec<O>
+            where T: Stateless<Self::Input, Output=O>;
+
+        /// Folds the transduced values into an accumulator, starting from
+        /// `init`, until `f` returns `Stop` or the values run out
+        fn transduce_fold_while<A, F, T, O, RO, E>(self, init: A, f: F, transducer: T) -> Result<A, E>
+            where F: Fn(A, O) -> (A, StepResult),
+                  RO: Reducing<Self::Input, A, E>,
+                  T: Transducer<FoldWhileReducer<A, F>, RO=RO>;
+
+        /// Counts the transduced values by the key given by `key`, the keys
+        /// are returned in the order they were first seen
+        #[cfg(feature = "std")]
+        fn transduce_count_by_ordered<E, K, F, T, O, RO>(self, key: F, transducer: T) -> Result<Vec<(K, usize)>, E>
+            where K: Eq + Hash + Clone,
+                  F: Fn(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<(K, usize)>, E>,
+                  T: Transducer<CountByReducer<K, F>, RO=RO>;
+
+        /// Groups the transduced values by the key given by `key`, unlike
+        /// `partition_by` the values of a group need not be adjacent.  Groups
+        /// are returned in the order their keys were first seen and values
+        /// in the order they arrived.  Every value is held until the source
+        /// is exhausted.
+        #[cfg(feature = "std")]
+        fn transduce_group_into_vec<E, K, F, T, O, RO>(self, key: F, transducer: T) -> Result<Vec<(K, Vec<O>)>, E>
+            where K: Eq + Hash + Clone,
+                  F: Fn(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<(K, Vec<O>)>, E>,
+                  T: Transducer<GroupByReducer<K, F, O>, RO=RO>;
+
+        /// As `transduce_into`, also returning the counts of the stages
+        /// wrapped with `stats.stage`.  `build` is given a fresh `Stats`
+        /// for this call to build the transducer with
+        fn transduce_into_instrumented<E, F, T, O, RO>(self, build: F)
+                                                       -> Result<(Vec<O>, Vec<StageStats>), E>
+            where F: FnOnce(&Stats) -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// As `transduce_into`, also returning whether the transducer
+        /// stopped before the vector was exhausted, e.g. to tell taking all
+        /// of five values from taking five of many
+        fn transduce_into_with_stop<E, T, O, RO>(self, transducer: T) -> Result<(Vec<O>, bool), E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// As `transduce_into`, but the results are appended to `target`
+        /// rather than to a new `Vec`, so several sources can be collected
+        /// into one.  On an error `target` keeps the values appended so far.
+        fn transduce_append<E, T, O, RO>(self, target: &mut Vec<O>, transducer: T) -> Result<(), E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// Transduces the vector `n` times in sequence, collecting every run
+        /// into one `Vec`.  Transducers keep their state between values, so
+        /// `make` is called to build a fresh one for each run.
+        fn transduce_cycle<E, F, T, O, RO>(self, n: usize, make: F) -> Result<Vec<O>, E>
+            where Self::Input: Clone,
+                  F: FnMut() -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// Passes each transduced value, along with `ctx`, to `f`, for sinks
+        /// that need access to some externally owned context
+        fn transduce_into_with<'a, C, F, T, O, RO, E>(self, ctx: &'a mut C, f: F, transducer: T) -> Result<(), E>
+            where F: FnMut(&mut C, O),
+                  RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<ContextReducer<'a, C, F>, RO=RO>;
+
+        /// As `transduce_into`, but gives `None` unless exactly `N` values
+        /// are produced, e.g. after a `take(N)`
+        fn transduce_into_array<const N: usize, E, T, O, RO>(self, transducer: T)
+                                                             -> Result<Option<[O; N]>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// Moves the vector into an iterator that applies the transducer
+        /// lazily, as values are requested
+        fn into_iter_transduced<T, O, RO, E>(self, transducer: T)
+                                             -> TransduceIterator<IntoIter<Self::Input>, O, RO>
+            where RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO>;
+
+        /// The same as `into_iter_transduced`
+        fn transduce_lazy<T, O, RO, E>(self, transducer: T)
+                                       -> TransduceIterator<IntoIter<Self::Input>, O, RO>
+            where RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO>;
+
+        /// Collects into a `Vec` kept in ascending order as each value
+        /// arrives.  Each insertion may shift the values after it, so for
+        /// large outputs collecting and then sorting is faster.
+        fn transduce_into_sorted<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where O: Ord,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<SortedVecReducer<O, fn(&O, &O) -> Ordering>, RO=RO>;
+
+        /// As `transduce_into_sorted`, but ordered by the given comparator
+        fn transduce_into_sorted_by<E, T, O, F, RO>(self,
+                                                    transducer: T,
+                                                    compare: F) -> Result<Vec<O>, E>
+            where F: Fn(&O, &O) -> Ordering,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<SortedVecReducer<O, F>, RO=RO>;
+
+        /// Collects into a `BinaryHeap` and returns its values in ascending
+        /// order once the transducer completes, for outputs too large to be
+        /// kept sorted by `transduce_into_sorted`
+        fn transduce_into_sorted_vec<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where O: Ord,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<HeapReducer<O>, RO=RO>;
+
+        /// As `transduce_into_sorted_vec`, but ordered by the given
+        /// comparator.  The sort is stable.
+        fn transduce_into_sorted_vec_by<E, T, O, F, RO>(self,
+                                                        transducer: T,
+                                                        compare: F) -> Result<Vec<O>, E>
+            where F: FnMut(&O, &O) -> Ordering,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// As `transduce_into_sorted_vec`, but ordered by the key `key`
+        /// gives for each value.  The sort is stable.
+        fn transduce_into_sorted_vec_by_key<E, T, O, K, F, RO>(self,
+                                                               transducer: T,
+                                                               key: F) -> Result<Vec<O>, E>
+            where K: Ord,
+                  F: FnMut(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+
+        /// Joins the `Display` form of each value into a single `String`,
+        /// with `sep` placed between values but not at either end
+        fn transduce_join<T, O, RO, E>(self, sep: &str, transducer: T) -> Result<String, E>
+            where O: Display,
+                  RO: Reducing<Self::Input, String, E>,
+                  T: Transducer<JoinReducer<O>, RO=RO>;
+
+        /// Applies a fresh transducer, built by `transducer`, to each chunk
+        /// of at most `chunk_size` values.  Stateful transducers therefore
+        /// only see one chunk at a time, e.g. a `take` will take from every
+        /// chunk.
+        fn transduce_chunks<E, F, T, O, RO>(self,
+                                            chunk_size: usize,
+                                            transducer: F) -> Result<Vec<O>, E>
+            where F: Fn() -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<ChunkReducer<VecReducer<O, E>>, RO=RO>;
+
+        /// As `transduce_chunks`, but each chunk feeds the given reducing
+        /// function, which is completed once every chunk has been processed
+        /// and then returned
+        fn transduce_chunks_with_reducer<F, T, R, RO, V, OF, E>(self,
+                                                                chunk_size: usize,
+                                                                transducer: F,
+                                                                reducer: R) -> Result<R, E>
+            where F: Fn() -> T,
+                  R: Reducing<V, OF, E>,
+                  RO: Reducing<Self::Input, OF, E>,
+                  T: Transducer<ChunkReducer<R>, RO=RO>;
+    }
+
+    /// Collects into a `Vec`, `E` is the error type of the reducing
+    /// functions above it
+    pub struct VecReducer<O, E = ()>(pub(super) Rc<RefCell<Vec<O>>>, pub(super) PhantomData<E>);
+
+    impl<O, E> Reducing<O, Vec<O>, E> for VecReducer<O, E> {
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, E> {
+            self.0.borrow_mut().push(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    pub struct SortedVecReducer<O, F> {
+        res: Rc<RefCell<Vec<O>>>,
+        compare: F
+    }
+
+    impl<O, F, E> Reducing<O, Vec<O>, E> for SortedVecReducer<O, F>
+        where F: Fn(&O, &O) -> Ordering {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, E> {
+            let mut res = self.res.borrow_mut();
+            let idx = res.partition_point(|x| (self.compare)(x, &value) != Ordering::Greater);
+            res.insert(idx, value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    pub struct HeapReducer<O>(Rc<RefCell<BinaryHeap<O>>>);
+
+    impl<O, E> Reducing<O, Vec<O>, E> for HeapReducer<O>
+        where O: Ord {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, E> {
+            self.0.borrow_mut().push(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub struct CountByReducer<K, F> {
+        counts: Rc<RefCell<Vec<(K, usize)>>>,
+        indices: HashMap<K, usize>,
+        key: F
+    }
+
+    #[cfg(feature = "std")]
+    impl<K, F, O, E> Reducing<O, Vec<(K, usize)>, E> for CountByReducer<K, F>
+        where K: Eq + Hash + Clone,
+              F: Fn(&O) -> K {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, E> {
+            let key = (self.key)(&value);
+            let mut counts = self.counts.borrow_mut();
+            match self.indices.get(&key) {
+                Some(&idx) => counts[idx].1 += 1,
+                None => {
+                    self.indices.insert(key.clone(), counts.len());
+                    counts.push((key, 1));
+                }
+            }
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    /// Groups of values paired with their key, in first seen order
+    #[cfg(feature = "std")]
+    type Groups<K, O> = Vec<(K, Vec<O>)>;
+
+    #[cfg(feature = "std")]
+    pub struct GroupByReducer<K, F, O> {
+        groups: Rc<RefCell<Groups<K, O>>>,
+        indices: HashMap<K, usize>,
+        key: F
+    }
+
+    #[cfg(feature = "std")]
+    impl<K, F, O, E> Reducing<O, Vec<(K, Vec<O>)>, E> for GroupByReducer<K, F, O>
+        where K: Eq + Hash + Clone,
+              F: Fn(&O) -> K {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, E> {
+            let key = (self.key)(&value);
+            let mut groups = self.groups.borrow_mut();
+            match self.indices.get(&key) {
+                Some(&idx) => groups[idx].1.push(value),
+                None => {
+                    self.indices.insert(key.clone(), groups.len());
+                    groups.push((key, vec![value]));
+                }
+            }
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    pub struct ContextReducer<'a, C, F> {
+        ctx: &'a mut C,
+        f: F
+    }
+
+    impl<'a, C, F, O> Reducing<O, (), ()> for ContextReducer<'a, C, F>
+        where F: FnMut(&mut C, O) {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, ()> {
+            (self.f)(self.ctx, value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    /// Once `f` has returned `Stop` any further values, such as those
+    /// flushed by a buffering transducer on completion, are ignored
+    pub struct FoldWhileReducer<A, F> {
+        acc: Rc<RefCell<Option<A>>>,
+        f: F,
+        stopped: bool
+    }
+
+    impl<A, F, O> Reducing<O, A, ()> for FoldWhileReducer<A, F>
+        where F: Fn(A, O) -> (A, StepResult) {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, ()> {
+            if self.stopped {
+                return Ok(StepResult::Stop);
+            }
+            let mut acc = self.acc.borrow_mut();
+            let (next, result) = (self.f)(acc.take().unwrap(), value);
+            *acc = Some(next);
+            if let StepResult::Stop = result {
+                self.stopped = true;
+            }
+            Ok(result)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    pub struct PartitionResultsReducer<O, FE>(Rc<RefCell<(Vec<O>, Vec<FE>)>>);
+
+    impl<O, FE> Reducing<Result<O, FE>, (Vec<O>, Vec<FE>), ()> for PartitionResultsReducer<O, FE> {
+        type Item = Result<O, FE>;
+
+        #[inline]
+        fn step(&mut self, value: Result<O, FE>) -> Result<StepResult, ()> {
+            let mut res = self.0.borrow_mut();
+            match value {
+                Ok(v) => res.0.push(v),
+                Err(e) => res.1.push(e)
+            }
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    pub struct UnzipReducer<A, B, F, G> {
+        res: Rc<RefCell<(Vec<A>, Vec<B>)>>,
+        f: F,
+        g: G
+    }
+
+    impl<A, B, XA, XB, F, G> Reducing<(XA, XB), (Vec<A>, Vec<B>), ()> for UnzipReducer<A, B, F, G>
+        where F: Fn(XA) -> A,
+              G: Fn(XB) -> B {
+
+        type Item = (XA, XB);
+
+        #[inline]
+        fn step(&mut self, value: (XA, XB)) -> Result<StepResult, ()> {
+            let mut res = self.res.borrow_mut();
+            res.0.push((self.f)(value.0));
+            res.1.push((self.g)(value.1));
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    pub struct JoinReducer<O> {
+        res: Rc<RefCell<String>>,
+        sep: String,
+        first: bool,
+        o_type: PhantomData<O>
+    }
+
+    impl<O> Reducing<O, String, ()> for JoinReducer<O>
+        where O: Display {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, ()> {
+            let mut res = self.res.borrow_mut();
+            if self.first {
+                self.first = false;
+            } else {
+                res.push_str(&self.sep);
+            }
+            let _ = write!(res, "{}", value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    struct ChunkState<R> {
+        rf: R,
+        stopped: bool
+    }
+
+    /// Shares one reducing function between the transducers applied to each
+    /// chunk, which is only completed once all chunks are done
+    pub struct ChunkReducer<R>(Rc<RefCell<ChunkState<R>>>);
+
+    impl<I, OF, E, R> Reducing<I, OF, E> for ChunkReducer<R>
+        where R: Reducing<I, OF, E> {
+
+        type Item = R::Item;
+
+        #[inline]
+        fn step(&mut self, value: I) -> Result<StepResult, E> {
+            let mut state = self.0.borrow_mut();
+            let result = state.rf.step(value)?;
+            if let StepResult::Stop = result {
+                state.stopped = true;
+            }
+            Ok(result)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    impl<X> Ref for Vec<X> {
+        type Input = X;
+
+        fn transduce_ref<'a, E, T, O, RO>(&'a self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<&'a Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            transduce_sized(self.iter(), self.len(), transducer)
+        }
+    }
+
+    impl<X> Drain for Vec<X> {
+        type Input = X;
+
+        fn transduce_drain<E, T, O, RO>(&mut self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self.drain(..), len, transducer)
+        }
+    }
+
+    impl<X> Unzip for Vec<X> {
+        type Input = X;
+
+        fn transduce_unzip<T, A, B, RO, E>(self, transducer: T) -> Result<(Vec<A>, Vec<B>), E>
+            where RO: Reducing<Self::Input, (Vec<A>, Vec<B>), E>,
+                  T: Transducer<UnzipReducer<A, B, fn(A) -> A, fn(B) -> B>, RO=RO> {
+            self.transduce_unzip_with(transducer, convert::identity, convert::identity)
+        }
+
+        fn transduce_unzip_with<T, A, B, F, G, RO, E>(self,
+                                                      transducer: T,
+                                                      f: F,
+                                                      g: G) -> Result<(Vec<A>, Vec<B>), E>
+            where RO: Reducing<Self::Input, (Vec<A>, Vec<B>), E>,
+                  T: Transducer<UnzipReducer<A, B, F, G>, RO=RO> {
+            let res = Rc::new(RefCell::new((Vec::with_capacity(self.len()),
+                                            Vec::with_capacity(self.len()))));
+            reduce(self, transducer.new(UnzipReducer {
+                res: res.clone(),
+                f,
+                g
+            }))?;
+            Ok(unwrap_shared(res))
+        }
+    }
+
+    impl<X> Into for Vec<X> {
+        type Input = X;
+
+        fn transduce_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
+            reduce(self, transducer.new(VecReducer(res.clone(), PhantomData)))?;
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            self.transduce_into(transducer)
+        }
+
+        fn transduce_collect_partial<E, T, O, RO>(self, transducer: T) -> (Vec<O>, Option<E>)
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
+            let err = reduce(self, transducer.new(VecReducer(res.clone(), PhantomData))).err();
+            (unwrap_shared(res), err)
+        }
+
+        fn transduce_partition_results<T, O, FE, RO, E>(self, transducer: T)
+                                                        -> Result<(Vec<O>, Vec<FE>), E>
+            where RO: Reducing<Self::Input, (Vec<O>, Vec<FE>), E>,
+                  T: Transducer<PartitionResultsReducer<O, FE>, RO=RO> {
+            let res = Rc::new(RefCell::new((Vec::new(), Vec::new())));
+            reduce(self, transducer.new(PartitionResultsReducer(res.clone())))?;
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_into_or_move<E, T, RO>(self, transducer: T) -> Result<Vec<Self::Input>, E>
+            where RO: Reducing<Self::Input, Vec<Self::Input>, E>,
+                  T: Transducer<VecReducer<Self::Input, E>, RO=RO> + 'static {
+            if TypeId::of::<T>() == TypeId::of::<IdentityTransducer>() {
+                return Ok(self);
+            }
+            self.transduce_into(transducer)
+        }
+
+        fn transduce_stateless_into<T, O>(self, transducer: T) -> Vec<O>
+            where T: Stateless<Self::Input, Output=O> {
+            let mut res = Vec::with_capacity(self.len());
+            for value in self {
+                res.push(transducer.apply_one(value));
+            }
+            res
+        }
+
+        fn transduce_fold_while<A, F, T, O, RO, E>(self, init: A, f: F, transducer: T) -> Result<A, E>
+            where F: Fn(A, O) -> (A, StepResult),
+                  RO: Reducing<Self::Input, A, E>,
+                  T: Transducer<FoldWhileReducer<A, F>, RO=RO> {
+            let acc = Rc::new(RefCell::new(Some(init)));
+            reduce(self, transducer.new(FoldWhileReducer {
+                acc: acc.clone(),
+                f,
+                stopped: false
+            }))?;
+            Ok(unwrap_shared(acc).unwrap())
+        }
+
+        #[cfg(feature = "std")]
+        fn transduce_count_by_ordered<E, K, F, T, O, RO>(self, key: F, transducer: T) -> Result<Vec<(K, usize)>, E>
+            where K: Eq + Hash + Clone,
+                  F: Fn(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<(K, usize)>, E>,
+                  T: Transducer<CountByReducer<K, F>, RO=RO> {
+            let counts = Rc::new(RefCell::new(Vec::new()));
+            reduce(self, transducer.new(CountByReducer {
+                counts: counts.clone(),
+                indices: HashMap::new(),
+                key
+            }))?;
+            Ok(unwrap_shared(counts))
+        }
+
+        #[cfg(feature = "std")]
+        fn transduce_group_into_vec<E, K, F, T, O, RO>(self, key: F, transducer: T) -> Result<Vec<(K, Vec<O>)>, E>
+            where K: Eq + Hash + Clone,
+                  F: Fn(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<(K, Vec<O>)>, E>,
+                  T: Transducer<GroupByReducer<K, F, O>, RO=RO> {
+            let groups = Rc::new(RefCell::new(Vec::new()));
+            reduce(self, transducer.new(GroupByReducer {
+                groups: groups.clone(),
+                indices: HashMap::new(),
+                key
+            }))?;
+            Ok(unwrap_shared(groups))
+        }
+
+        fn transduce_into_instrumented<E, F, T, O, RO>(self, build: F)
+                                                       -> Result<(Vec<O>, Vec<StageStats>), E>
+            where F: FnOnce(&Stats) -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let stats = Stats::new();
+            let res = self.transduce_into(build(&stats))?;
+            Ok((res, stats.snapshot()))
+        }
+
+        fn transduce_into_with_stop<E, T, O, RO>(self, transducer: T) -> Result<(Vec<O>, bool), E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            let res = Rc::new(RefCell::new(Vec::with_capacity(len)));
+            let mut truncated = false;
+            {
+                let mut rf = transducer.new(VecReducer(res.clone(), PhantomData));
+                rf.init();
+                for (idx, val) in self.into_iter().enumerate() {
+                    if let StepResult::Stop = rf.step(val)? {
+                        truncated = idx + 1 < len;
+                        break;
+                    }
+                }
+                rf.complete()?;
+            }
+            Ok((unwrap_shared(res), truncated))
+        }
+
+        fn transduce_append<E, T, O, RO>(self, target: &mut Vec<O>, transducer: T) -> Result<(), E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            target.reserve(self.len());
+            let res = Rc::new(RefCell::new(mem::take(target)));
+            let result = reduce(self, transducer.new(VecReducer(res.clone(), PhantomData)));
+            *target = unwrap_shared(res);
+            result
+        }
+
+        fn transduce_cycle<E, F, T, O, RO>(self, n: usize, mut make: F) -> Result<Vec<O>, E>
+            where Self::Input: Clone,
+                  F: FnMut() -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len() * n)));
+            for _ in 0..n {
+                reduce(self.iter().cloned(), make().new(VecReducer(res.clone(), PhantomData)))?;
+            }
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_into_with<'a, C, F, T, O, RO, E>(self, ctx: &'a mut C, f: F, transducer: T) -> Result<(), E>
+            where F: FnMut(&mut C, O),
+                  RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<ContextReducer<'a, C, F>, RO=RO> {
+            reduce(self, transducer.new(ContextReducer {
+                ctx,
+                f
+            }))
+        }
+
+        fn transduce_into_array<const N: usize, E, T, O, RO>(self, transducer: T)
+                                                             -> Result<Option<[O; N]>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let res = self.transduce_into(transducer)?;
+            Ok(<[O; N]>::try_from(res).ok())
+        }
+
+        fn into_iter_transduced<T, O, RO, E>(self, transducer: T)
+                                             -> TransduceIterator<IntoIter<Self::Input>, O, RO>
+            where RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO> {
+            self.into_iter().transduce(transducer)
+        }
+
+        fn transduce_lazy<T, O, RO, E>(self, transducer: T)
+                                       -> TransduceIterator<IntoIter<Self::Input>, O, RO>
+            where RO: Reducing<Self::Input, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO> {
+            self.into_iter_transduced(transducer)
+        }
+
+        fn transduce_into_sorted<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where O: Ord,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<SortedVecReducer<O, fn(&O, &O) -> Ordering>, RO=RO> {
+            self.transduce_into_sorted_by(transducer, Ord::cmp)
+        }
+
+        fn transduce_into_sorted_by<E, T, O, F, RO>(self,
+                                                    transducer: T,
+                                                    compare: F) -> Result<Vec<O>, E>
+            where F: Fn(&O, &O) -> Ordering,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<SortedVecReducer<O, F>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
+            reduce(self, transducer.new(SortedVecReducer {
+                res: res.clone(),
+                compare
+            }))?;
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_into_sorted_vec<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where O: Ord,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<HeapReducer<O>, RO=RO> {
+            let heap = Rc::new(RefCell::new(BinaryHeap::with_capacity(self.len())));
+            reduce(self, transducer.new(HeapReducer(heap.clone())))?;
+            Ok(unwrap_shared(heap).into_sorted_vec())
+        }
+
+        fn transduce_into_sorted_vec_by<E, T, O, F, RO>(self,
+                                                        transducer: T,
+                                                        compare: F) -> Result<Vec<O>, E>
+            where F: FnMut(&O, &O) -> Ordering,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let mut res = self.transduce_into(transducer)?;
+            res.sort_by(compare);
+            Ok(res)
+        }
+
+        fn transduce_into_sorted_vec_by_key<E, T, O, K, F, RO>(self,
+                                                               transducer: T,
+                                                               key: F) -> Result<Vec<O>, E>
+            where K: Ord,
+                  F: FnMut(&O) -> K,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let mut res = self.transduce_into(transducer)?;
+            res.sort_by_key(key);
+            Ok(res)
+        }
+
+        fn transduce_join<T, O, RO, E>(self, sep: &str, transducer: T) -> Result<String, E>
+            where O: Display,
+                  RO: Reducing<Self::Input, String, E>,
+                  T: Transducer<JoinReducer<O>, RO=RO> {
+            let res = Rc::new(RefCell::new(String::new()));
+            reduce(self, transducer.new(JoinReducer {
+                res: res.clone(),
+                sep: sep.to_string(),
+                first: true,
+                o_type: PhantomData
+            }))?;
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_chunks<E, F, T, O, RO>(self,
+                                            chunk_size: usize,
+                                            transducer: F) -> Result<Vec<O>, E>
+            where F: Fn() -> T,
+                  RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<ChunkReducer<VecReducer<O, E>>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
+            reduce_chunks(self, chunk_size, transducer, VecReducer(res.clone(), PhantomData))?;
+            Ok(unwrap_shared(res))
+        }
+
+        fn transduce_chunks_with_reducer<F, T, R, RO, V, OF, E>(self,
+                                                                chunk_size: usize,
+                                                                transducer: F,
+                                                                mut reducer: R) -> Result<R, E>
+            where F: Fn() -> T,
+                  R: Reducing<V, OF, E>,
+                  RO: Reducing<Self::Input, OF, E>,
+                  T: Transducer<ChunkReducer<R>, RO=RO> {
+            reducer.init();
+            reducer = reduce_chunks(self, chunk_size, transducer, reducer)?;
+            reducer.complete()?;
+            Ok(reducer)
+        }
+    }
+
+    fn reduce_chunks<X, F, T, R, RO, OF, E>(source: Vec<X>,
+                                            chunk_size: usize,
+                                            transducer: F,
+                                            reducer: R) -> Result<R, E>
+        where F: Fn() -> T,
+              RO: Reducing<X, OF, E>,
+              T: Transducer<ChunkReducer<R>, RO=RO> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let state = Rc::new(RefCell::new(ChunkState {
+            rf: reducer,
+            stopped: false
+        }));
+        let mut source = source.into_iter();
+        while source.len() > 0 {
+            let chunk:Vec<X> = source.by_ref().take(chunk_size).collect();
+            reduce(chunk, transducer().new(ChunkReducer(state.clone())))?;
+            if state.borrow().stopped {
+                break;
+            }
+        }
+        Ok(unwrap_shared(state).rf)
+    }
+
+    /// A push-based application, values are stepped through the transducer
+    /// one at a time as they become available.  Nothing is returned, and
+    /// buffered values are not flushed, until `finish` is called.
+    #[must_use = "values pushed into a Sink are only returned by finish"]
+    pub struct Sink<I, O, RO> {
+        rf: RO,
+        res: Rc<RefCell<Vec<O>>>,
+        started: bool,
+        stopped: bool,
+        i_type: PhantomData<I>
+    }
+
+    impl<I, O, RO> Sink<I, O, RO> {
+        /// Results are appended to `target`
+        pub fn new<T>(target: Vec<O>, transducer: T) -> Self
+            where T: Transducer<VecReducer<O>, RO=RO> {
+            let res = Rc::new(RefCell::new(target));
+            Sink {
+                rf: transducer.new(VecReducer(res.clone(), PhantomData)),
+                res,
+                started: false,
+                stopped: false,
+                i_type: PhantomData
+            }
+        }
+
+        /// Once `Stop` has been returned any further values are ignored
+        pub fn push<E>(&mut self, value: I) -> Result<StepResult, E>
+            where RO: Reducing<I, Vec<O>, E> {
+            self.start();
+            if self.stopped {
+                return Ok(StepResult::Stop)
+            }
+            let result = self.rf.step(value)?;
+            if let StepResult::Stop = result {
+                self.stopped = true;
+            }
+            Ok(result)
+        }
+
+        /// Completes the transducer and returns the results.  The `Sink` is
+        /// consumed, so it cannot be pushed to afterwards:
+        ///
+        /// ```compile_fail,E0382
+        /// use rs_transducers::transducers;
+        /// use rs_transducers::applications::vec::Sink;
+        ///
+        /// let mut sink = Sink::new(Vec::new(), transducers::partition_all(2));
+        /// sink.push::<()>(1).unwrap();
+        /// let result = sink.finish::<()>().unwrap();
+        /// sink.push::<()>(2).unwrap();
+        /// ```
+        pub fn finish<E>(mut self) -> Result<Vec<O>, E>
+            where RO: Reducing<I, Vec<O>, E> {
+            self.start();
+            let Sink { mut rf, res, .. } = self;
+            rf.complete()?;
+            drop(rf);
+            Ok(unwrap_shared(res))
+        }
+
+        fn start<E>(&mut self)
+            where RO: Reducing<I, Vec<O>, E> {
+            if !self.started {
+                self.started = true;
+                self.rf.init();
+            }
+        }
+    }
+}
+
+/// Applies transducers to the `(K, V)` entries of maps.  A `BTreeMap` is
+/// transduced in key order, a `HashMap` in an arbitrary order.
+pub mod map {
+    use alloc::collections::BTreeMap;
+    use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+    #[cfg(feature = "std")]
+    use std::hash::Hash;
+
+    use ::{Transducer, Reducing};
+    use super::transduce_sized;
+    use super::vec::VecReducer;
+
+    pub trait Into {
+        type Input;
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO>;
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+    }
+
+    impl<K, V> Into for BTreeMap<K, V> {
+        type Input = (K, V);
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<K, V> Into for HashMap<K, V>
+        where K: Eq + Hash {
+
+        type Input = (K, V);
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+    }
+}
+
+/// Applies transducers to the other sequence collections, `VecDeque`,
+/// `LinkedList` and arrays, consuming them front to back
+pub mod collections {
+    use alloc::collections::{LinkedList, VecDeque};
+    use alloc::vec::Vec;
+
+    use ::{Transducer, Reducing};
+    use super::transduce_sized;
+    use super::vec::VecReducer;
+
+    pub trait Into {
+        type Input;
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO>;
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO>;
+    }
+
+    impl<X> Into for VecDeque<X> {
+        type Input = X;
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+    }
+
+    impl<X> Into for LinkedList<X> {
+        type Input = X;
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let len = self.len();
+            transduce_sized(self, len, transducer)
+        }
+    }
+
+    impl<X, const N: usize> Into for [X; N] {
+        type Input = X;
+
+        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O>, RO=RO> {
+            transduce_sized(self, N, transducer)
+        }
+
+        fn transduce_try_into<E, T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<Self::Input, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            transduce_sized(self, N, transducer)
+        }
+    }
+}
+
+pub mod iter {
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+    use core::iter::FusedIterator;
+    use core::marker::PhantomData;
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::unwrap_shared;
+    use super::vec::VecReducer;
+
+    pub trait TransduceIter {
+        type UnderlyingIterator;
+        type Item;
+
+        fn transduce<T, O, RO, E>(self, transducer: T) -> TransduceIterator<Self::UnderlyingIterator, O, RO>
+            where RO: Reducing<Self::Item, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO>;
+    }
+
+    impl<I, T> TransduceIter for I
+        where I: Iterator<Item=T> {
+
+        type UnderlyingIterator = I;
+        type Item = T;
+
+        fn transduce<TR, O, RO, E>(self, transducer: TR) -> TransduceIterator<Self::UnderlyingIterator, O, RO>
+            where RO: Reducing<Self::Item, (), E>,
+                  TR: Transducer<IterReducer<O>, RO=RO> {
+            let buffer = Rc::new(RefCell::new(VecDeque::new()));
+
+            TransduceIterator {
+                underlying: self,
+                buffer: buffer.clone(),
+                rf: transducer.new(IterReducer(buffer.clone())),
+                runoff: false
+            }
+        }
+    }
+
+    pub trait TransduceResults {
+        type Item;
+        type Error;
+
+        /// Steps each `Ok` value of a fallible source through the
+        /// transducer, collecting into a new `Vec`.  The first `Err` from the
+        /// source aborts with that error, as does any error from the
+        /// transducer, which must share the source's error type.
+        fn transduce_results<T, O, RO>(self, transducer: T) -> Result<Vec<O>, Self::Error>
+            where RO: Reducing<Self::Item, Vec<O>, Self::Error>,
+                  T: Transducer<VecReducer<O, Self::Error>, RO=RO>;
+    }
+
+    impl<I, X, E> TransduceResults for I
+        where I: Iterator<Item=Result<X, E>> {
+
+        type Item = X;
+        type Error = E;
+
+        fn transduce_results<T, O, RO>(self, transducer: T) -> Result<Vec<O>, E>
+            where RO: Reducing<X, Vec<O>, E>,
+                  T: Transducer<VecReducer<O, E>, RO=RO> {
+            let res = Rc::new(RefCell::new(Vec::new()));
+            let mut rf = transducer.new(VecReducer(res.clone(), PhantomData));
+            rf.init();
+            for value in self {
+                if let StepResult::Stop = rf.step(value?)? {
+                    break;
+                }
+            }
+            rf.complete()?;
+            drop(rf);
+            Ok(unwrap_shared(res))
+        }
+    }
+
+    pub struct IterReducer<T>(Rc<RefCell<VecDeque<T>>>);
+
+    impl<T> Reducing<T, (), ()> for IterReducer<T> {
+        type Item = T;
+
+        #[inline]
+        fn step(&mut self, value: T) -> Result<StepResult, ()> {
+            self.0.borrow_mut().push_back(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    /// Pulls values through the transducer on demand.  All state lives in
+    /// the iterator itself, so it can be partially consumed with `by_ref`
+    /// and later resumed where it left off.
+    pub struct TransduceIterator<I, O, RF> {
+        underlying: I,
+        buffer: Rc<RefCell<VecDeque<O>>>,
+        rf: RF,
+        runoff: bool
+    }
+
+    impl<I, IN, O, RF> Iterator for TransduceIterator<I, O, RF>
+        where I: Iterator<Item=IN>,
+              RF: Reducing<IN, (), ()> {
+
+        type Item = O;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if !self.runoff && self.buffer.borrow().is_empty() {
+                    match self.underlying.next() {
+                        None => {
+                            self.runoff = true;
+                            self.rf.complete().unwrap();
+                        },
+                        Some(value) => {
+                            match self.rf.step(value) {
+                                Ok(StepResult::Continue) => (),
+                                Ok(StepResult::Stop) => {
+                                    self.runoff = true;
+                                    self.rf.complete().unwrap();
+                                },
+                                Err(_) => unreachable!()
+                            }
+                        }
+                    }
+                }
+                if self.runoff && self.buffer.borrow().is_empty() {
+                    return None
+                }
+                match self.buffer.borrow_mut().pop_front() {
+                    None => (),
+                    Some(value) => return Some(value)
+                }
+            }
+        }
+    }
+
+    /// Shortcuts for transducers producing `Result`s
+    impl<I, IN, O, E, RF> TransduceIterator<I, Result<O, E>, RF>
+        where I: Iterator<Item=IN>,
+              RF: Reducing<IN, (), ()> {
+
+        /// Collects the `Ok` values, stopping at the first error
+        pub fn collect_results(self) -> Result<Vec<O>, E> {
+            self.collect()
+        }
+
+        /// Collects the `Ok` values, discarding any errors
+        pub fn collect_ok(self) -> Vec<O> {
+            self.filter_map(Result::ok).collect()
+        }
+
+        /// Collects the `Ok` values and the errors separately
+        pub fn collect_all(self) -> (Vec<O>, Vec<E>) {
+            let mut oks = Vec::new();
+            let mut errs = Vec::new();
+            for result in self {
+                match result {
+                    Ok(value) => oks.push(value),
+                    Err(e) => errs.push(e)
+                }
+            }
+            (oks, errs)
+        }
+    }
+
+    impl<I, IN, O, RF> FusedIterator for TransduceIterator<I, O, RF>
+        where I: Iterator<Item=IN>,
+              RF: Reducing<IN, (), ()> {}
+}
+
+/// Drives a source through a transducer with asynchronous stages.  The
+/// returned future can be awaited on any executor.
+pub mod async_iter {
+    use alloc::boxed::Box;
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::{Cell, RefCell};
+    use core::future::Future;
+    use core::marker::PhantomData;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::unwrap_shared;
+    use super::vec::VecReducer;
+
+    type Job<'a, E> = Pin<Box<dyn Future<Output = Result<StepResult, E>> + 'a>>;
+
+    /// Steps and completions deferred by `async_map` stages, awaited in
+    /// order by `TransduceAsync`
+    type Jobs<'a, E> = Rc<RefCell<VecDeque<Job<'a, E>>>>;
+
+    /// Passed to the function given to `transduce_async`, for building its
+    /// asynchronous stages
+    pub struct AsyncContext<'a, E> {
+        jobs: Jobs<'a, E>
+    }
+
+    impl<'a, E> AsyncContext<'a, E> {
+        /// Maps each value to a future, whose output is passed on once
+        /// awaited.  This is a transducer like any other, so it may be
+        /// composed anywhere in the pipeline, but is awaited only by the
+        /// `transduce_async` it was built for.
+        pub fn async_map<F, I, Fut>(&self, f: F) -> AsyncMapTransducer<'a, F, E>
+            where F: Fn(I) -> Fut,
+                  Fut: Future {
+
+            AsyncMapTransducer {
+                f,
+                jobs: self.jobs.clone()
+            }
+        }
+    }
+
+    #[must_use = "transducers do nothing unless applied"]
+    pub struct AsyncMapTransducer<'a, F, E> {
+        f: F,
+        jobs: Jobs<'a, E>
+    }
+
+    /// Defers each step to a job awaiting `f`'s future, so the reducing
+    /// functions below are stepped only once it is ready.  Completion is
+    /// deferred likewise, after the steps before it.
+    pub struct AsyncMapReducer<'a, R, F, E> {
+        rf: Rc<RefCell<R>>,
+        f: F,
+        jobs: Jobs<'a, E>,
+        stopped: Rc<Cell<bool>>
+    }
+
+    impl<'a, RI, F, E> Transducer<RI> for AsyncMapTransducer<'a, F, E> {
+        type RO = AsyncMapReducer<'a, RI, F, E>;
+
+        fn new(self, reducing_fn: RI) -> Self::RO {
+            AsyncMapReducer {
+                rf: Rc::new(RefCell::new(reducing_fn)),
+                f: self.f,
+                jobs: self.jobs,
+                stopped: Rc::new(Cell::new(false))
+            }
+        }
+    }
+
+    /// A step awaiting its value, or with none, a completion
+    struct AsyncStep<Fut, R, OF, E> {
+        future: Option<Pin<Box<Fut>>>,
+        rf: Rc<RefCell<R>>,
+        stopped: Rc<Cell<bool>>,
+        types: PhantomData<fn() -> (OF, E)>
+    }
+
+    impl<Fut, R, OF, E> Future for AsyncStep<Fut, R, OF, E>
+        where Fut: Future,
+              R: Reducing<Fut::Output, OF, E> {
+
+        type Output = Result<StepResult, E>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            let future = match this.future {
+                Some(ref mut future) => future,
+                None => return Poll::Ready(this.rf.borrow_mut().complete().map(|_| StepResult::Continue))
+            };
+            if this.stopped.get() {
+                return Poll::Ready(Ok(StepResult::Stop));
+            }
+            let value = match future.as_mut().poll(cx) {
+                Poll::Ready(value) => value,
+                Poll::Pending => return Poll::Pending
+            };
+            let result = this.rf.borrow_mut().step(value);
+            if let Ok(StepResult::Stop) = result {
+                this.stopped.set(true);
+            }
+            Poll::Ready(result)
+        }
+    }
+
+    impl<'a, R, F, I, Fut, OF, E> Reducing<I, OF, E> for AsyncMapReducer<'a, R, F, E>
+        where F: Fn(I) -> Fut,
+              Fut: Future + 'a,
+              R: Reducing<Fut::Output, OF, E> + 'a,
+              OF: 'a,
+              E: 'a {
+
+        type Item = Fut::Output;
+
+        fn init(&mut self) {
+            self.rf.borrow_mut().init();
+        }
+
+        #[inline]
+        fn step(&mut self, value: I) -> Result<StepResult, E> {
+            if self.stopped.get() {
+                return Ok(StepResult::Stop);
+            }
+            self.jobs.borrow_mut().push_back(Box::pin(AsyncStep::<_, _, OF, E> {
+                future: Some(Box::pin((self.f)(value))),
+                rf: self.rf.clone(),
+                stopped: self.stopped.clone(),
+                types: PhantomData
+            }));
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            self.jobs.borrow_mut().push_back(Box::pin(AsyncStep::<Fut, _, OF, E> {
+                future: None,
+                rf: self.rf.clone(),
+                stopped: self.stopped.clone(),
+                types: PhantomData
+            }));
+            Ok(())
+        }
+    }
+
+    /// Steps each value of `source` in turn through the transducer built by
+    /// `build`, awaiting the futures of its `async_map` stages as they are
+    /// reached and collecting into a new `Vec`.  The next value is taken
+    /// only once the futures of the last have been awaited, so values stay
+    /// in order.
+    pub fn transduce_async<'a, S, B, T, RO, O, E>(source: S, build: B) -> TransduceAsync<'a, S::IntoIter, RO, O, E>
+        where S: IntoIterator,
+              B: FnOnce(&AsyncContext<'a, E>) -> T,
+              RO: Reducing<S::Item, Vec<O>, E>,
+              T: Transducer<VecReducer<O>, RO=RO> {
+        let context = AsyncContext {
+            jobs: Rc::new(RefCell::new(VecDeque::new()))
+        };
+        let res = Rc::new(RefCell::new(Vec::new()));
+        let mut rf = build(&context).new(VecReducer(res.clone(), PhantomData));
+        rf.init();
+        TransduceAsync {
+            source: source.into_iter(),
+            rf: Some(rf),
+            jobs: context.jobs,
+            current: None,
+            completed: false,
+            res: Some(res)
+        }
+    }
+
+    /// The future returned by `transduce_async`
+    pub struct TransduceAsync<'a, S, RO, O, E> {
+        source: S,
+        rf: Option<RO>,
+        jobs: Jobs<'a, E>,
+        current: Option<Job<'a, E>>,
+        completed: bool,
+        res: Option<Rc<RefCell<Vec<O>>>>
+    }
+
+    impl<'a, S, RO, O, E> TransduceAsync<'a, S, RO, O, E>
+        where S: Iterator,
+              RO: Reducing<S::Item, Vec<O>, E> {
+
+        /// Completes the transducer, whose `async_map` stages then queue
+        /// their completions to be awaited
+        fn complete(&mut self) -> Result<(), E> {
+            self.completed = true;
+            self.rf.as_mut().unwrap().complete()
+        }
+    }
+
+    impl<'a, S, RO, O, E> Future for TransduceAsync<'a, S, RO, O, E>
+        where S: Iterator + Unpin,
+              RO: Reducing<S::Item, Vec<O>, E> + Unpin {
+
+        type Output = Result<Vec<O>, E>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            loop {
+                if let Some(ref mut job) = this.current {
+                    let result = match job.as_mut().poll(cx) {
+                        Poll::Ready(result) => result,
+                        Poll::Pending => return Poll::Pending
+                    };
+                    this.current = None;
+                    match result {
+                        Ok(StepResult::Continue) => (),
+                        Ok(StepResult::Stop) => if !this.completed {
+                            this.complete()?;
+                        },
+                        Err(e) => return Poll::Ready(Err(e))
+                    }
+                }
+                let job = this.jobs.borrow_mut().pop_front();
+                if job.is_some() {
+                    this.current = job;
+                    continue;
+                }
+                if this.completed {
+                    this.rf = None;
+                    return Poll::Ready(Ok(unwrap_shared(this.res.take().unwrap())));
+                }
+                let step = match this.source.next() {
+                    Some(value) => this.rf.as_mut().unwrap().step(value),
+                    None => Ok(StepResult::Stop)
+                };
+                match step {
+                    Ok(StepResult::Continue) => (),
+                    Ok(StepResult::Stop) => this.complete()?,
+                    Err(e) => return Poll::Ready(Err(e))
+                }
+            }
+        }
+    }
+}
+
+/// Applies transducers to the characters or bytes of strings, lazily or
+/// into a new `String`
+pub mod string {
+    use alloc::rc::Rc;
+    use alloc::string::String;
+    use core::cell::RefCell;
+    use core::str::{Bytes, Chars};
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::reduce;
+    use super::iter::{IterReducer, TransduceIter, TransduceIterator};
+
+    pub trait TransduceStr {
+        /// Transduces the characters of the string as an iterator, which
+        /// can be collected into a `String` if it yields `char`s
+        fn transduce_chars<'a, T, O, RO, E>(&'a self, transducer: T) -> TransduceIterator<Chars<'a>, O, RO>
+            where RO: Reducing<char, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO>;
+
+        /// Transduces the UTF-8 bytes of the string as an iterator
+        fn transduce_bytes<'a, T, O, RO, E>(&'a self, transducer: T) -> TransduceIterator<Bytes<'a>, O, RO>
+            where RO: Reducing<u8, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO>;
+
+        /// Transduces the characters of the string into a new `String`
+        fn transduce_into_string<T, RO, E>(&self, transducer: T) -> Result<String, E>
+            where RO: Reducing<char, String, E>,
+                  T: Transducer<StringReducer, RO=RO>;
+    }
+
+    /// Collects `char`s into a `String`
+    pub struct StringReducer(Rc<RefCell<String>>);
+
+    impl Reducing<char, String, ()> for StringReducer {
+        type Item = char;
+
+        #[inline]
+        fn step(&mut self, value: char) -> Result<StepResult, ()> {
+            self.0.borrow_mut().push(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    impl TransduceStr for str {
+        fn transduce_chars<'a, T, O, RO, E>(&'a self, transducer: T) -> TransduceIterator<Chars<'a>, O, RO>
+            where RO: Reducing<char, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO> {
+            self.chars().transduce(transducer)
+        }
+
+        fn transduce_bytes<'a, T, O, RO, E>(&'a self, transducer: T) -> TransduceIterator<Bytes<'a>, O, RO>
+            where RO: Reducing<u8, (), E>,
+                  T: Transducer<IterReducer<O>, RO=RO> {
+            self.bytes().transduce(transducer)
+        }
+
+        fn transduce_into_string<T, RO, E>(&self, transducer: T) -> Result<String, E>
+            where RO: Reducing<char, String, E>,
+                  T: Transducer<StringReducer, RO=RO> {
+            let res = Rc::new(RefCell::new(String::with_capacity(self.len())));
+            reduce(self.chars(), transducer.new(StringReducer(res.clone())))?;
+            Ok(super::unwrap_shared(res))
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod io {
+    use std::fmt::Display;
+    use std::io::{Error, Write};
+    use std::marker::PhantomData;
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::reduce;
+
+    pub trait WriteLines {
+        type Input;
+
+        /// Writes each value, followed by a newline, to `writer`.  The first
+        /// failed write aborts the reduction.
+        fn transduce_write_lines<W, T, O, RO>(self, writer: W, transducer: T) -> Result<(), Error>
+            where W: Write,
+                  O: Display,
+                  RO: Reducing<Self::Input, (), Error>,
+                  T: Transducer<WriteLinesReducer<W, O>, RO=RO>;
+    }
+
+    pub struct WriteLinesReducer<W, O> {
+        writer: W,
+        o_type: PhantomData<O>
+    }
+
+    impl<O, W> Reducing<O, (), Error> for WriteLinesReducer<W, O>
+        where O: Display,
+              W: Write {
+
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, Error> {
+            writeln!(self.writer, "{}", value)?;
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), Error> {
+            self.writer.flush()
+        }
+    }
+
+    impl<X> WriteLines for Vec<X> {
+        type Input = X;
+
+        fn transduce_write_lines<W, T, O, RO>(self, writer: W, transducer: T) -> Result<(), Error>
+            where W: Write,
+                  O: Display,
+                  RO: Reducing<Self::Input, (), Error>,
+                  T: Transducer<WriteLinesReducer<W, O>, RO=RO> {
+            reduce(self, transducer.new(WriteLinesReducer {
+                writer,
+                o_type: PhantomData
+            }))
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod channels {
+    use alloc::collections::VecDeque;
+    #[cfg(feature = "futures-sink")]
+    use core::task::{Context, Poll};
+    use core::task::Waker;
+    use std::error::Error;
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::mpsc::{Receiver, RecvError, RecvTimeoutError, Sender, SyncSender, SendError,
+                          TryRecvError, TrySendError, channel, sync_channel};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[cfg(feature = "metrics")]
+    use ::metrics::Gauge;
+
+    use ::{Transducer, Reducing, StepResult};
+    use ::transducers::{timestamp, TimestampReducer};
+    use super::reduce;
+    use super::iter::{IterReducer, TransduceIter, TransduceIterator};
+
+    pub struct TransducingSender<I, O, SR>
+        where SR: Reducing<I, (), SendError<O>> {
+
+        rf: SR,
+        stopped: bool,
+        i_type: PhantomData<I>,
+        o_type: PhantomData<O>
+    }
+
+    pub struct SenderReducer<T>(Sender<T>);
+
+    impl<O> Reducing<O, (), SendError<O>> for SenderReducer<O> {
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, SendError<O>> {
+            match self.0.send(value) {
+                Ok(_) => Ok(StepResult::Continue),
+                Err(e) => Err(e)
+            }
+        }
+
+        fn complete(&mut self) -> Result<(), SendError<O>> {
+            Ok(())
+        }
+    }
+
+    impl<I, O, SR> TransducingSender<I, O, SR>
+        where SR: Reducing<I, (), SendError<O>> {
+
+        pub(super) fn from_reducer(rf: SR) -> Self {
+            TransducingSender {
+                rf,
+                stopped: false,
+                i_type: PhantomData,
+                o_type: PhantomData
+            }
+        }
+
+        /// Returns `false` once the transducer has stopped, e.g. after a
+        /// `take`, the producer should then stop sending.  Values sent after
+        /// that point are dropped without reaching the transducer.
+        pub fn send(&mut self, f: I) -> Result<bool, SendError<O>> {
+            if self.stopped {
+                return Ok(false);
+            }
+            match self.rf.step(f) {
+                Ok(StepResult::Continue) => Ok(true),
+                Ok(StepResult::Stop) => {
+                    self.stopped = true;
+                    Ok(false)
+                },
+                Err(e) => Err(e)
+            }
+        }
+
+        /// Completes the transducer, flushing any values it holds
+        pub fn close(&mut self) -> Result<(), SendError<O>> {
+            self.rf.complete()
+        }
+
+        /// Completes the transducer, sending any values it still holds,
+        /// e.g. the final chunk of a `partition_all`, and then drops the
+        /// sender, so the receiver sees the channel disconnect once it has
+        /// received them
+        pub fn drain_and_close(mut self) -> Result<(), SendError<O>> {
+            self.close()
+        }
+    }
+
+    pub fn transducing_channel<I, O, T, RO>(transducer: T) -> (TransducingSender<I, O, RO>,
+                                                               Receiver<O>)
+        where RO: Reducing<I, (), SendError<O>>,
+              T: Transducer<SenderReducer<O>, RO=RO> {
+        let (tx, rx) = channel();
+        (TransducingSender::from_reducer(transducer.new(SenderReducer(tx))), rx)
+    }
+
+    pub struct BoundedTransducingSender<I, O, SR>
+        where SR: Reducing<I, (), TrySendError<O>> {
+
+        rf: SR,
+        shared: Arc<BoundedState<O>>,
+        #[cfg(feature = "futures-sink")]
+        tx: SyncSender<O>,
+        stopped: bool,
+        completed: bool,
+        i_type: PhantomData<I>,
+        o_type: PhantomData<O>
+    }
+
+    /// How a `SyncSenderReducer` handles a full channel
+    const BLOCK: u8 = 0;
+    const FAIL: u8 = 1;
+    #[cfg(feature = "futures-sink")]
+    const HOLD: u8 = 2;
+
+    /// Shared by both ends of a bounded transducing channel
+    struct BoundedState<T> {
+        mode: AtomicU8,
+        /// Values held back by a `Sink` until the channel has room, sent
+        /// before any others
+        overflow: Mutex<VecDeque<T>>,
+        /// Woken by the receiver as it makes room
+        waker: Mutex<Option<Waker>>,
+        /// The number of values in the channel, if tracked
+        #[cfg(feature = "metrics")]
+        depth: Option<Gauge>
+    }
+
+    impl<T> BoundedState<T> {
+        /// Counts a value before it is sent, so the receiver never sees the
+        /// depth fall below zero, and `unsent` if sending it then fails
+        #[inline]
+        fn sending(&self) {
+            #[cfg(feature = "metrics")]
+            if let Some(ref depth) = self.depth {
+                depth.increment(1.0);
+            }
+        }
+
+        #[inline]
+        fn unsent(&self) {
+            #[cfg(feature = "metrics")]
+            if let Some(ref depth) = self.depth {
+                depth.decrement(1.0);
+            }
+        }
+
+        #[inline]
+        fn received(&self) {
+            #[cfg(feature = "metrics")]
+            if let Some(ref depth) = self.depth {
+                depth.decrement(1.0);
+            }
+            if let Some(waker) = self.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+
+        fn send(&self, tx: &SyncSender<T>, value: T) -> Result<(), SendError<T>> {
+            self.sending();
+            tx.send(value).inspect_err(|_| self.unsent())
+        }
+
+        fn try_send(&self, tx: &SyncSender<T>, value: T) -> Result<(), TrySendError<T>> {
+            self.sending();
+            tx.try_send(value).inspect_err(|_| self.unsent())
+        }
+    }
+
+    /// Sends to a bounded channel, either blocking until there is capacity,
+    /// failing immediately, or holding the value back, depending on the mode
+    /// set by the sender
+    pub struct SyncSenderReducer<T> {
+        tx: SyncSender<T>,
+        shared: Arc<BoundedState<T>>
+    }
+
+    impl<O> Reducing<O, (), TrySendError<O>> for SyncSenderReducer<O> {
+        type Item = O;
+
+        #[inline]
+        fn step(&mut self, value: O) -> Result<StepResult, TrySendError<O>> {
+            let mut overflow = self.shared.overflow.lock().unwrap();
+            match self.shared.mode.load(Ordering::Relaxed) {
+                BLOCK => {
+                    for held in overflow.drain(..) {
+                        self.shared.send(&self.tx, held).map_err(|SendError(v)| TrySendError::Disconnected(v))?;
+                    }
+                    match self.shared.send(&self.tx, value) {
+                        Ok(_) => Ok(StepResult::Continue),
+                        Err(SendError(v)) => Err(TrySendError::Disconnected(v))
+                    }
+                },
+                FAIL if !overflow.is_empty() => Err(TrySendError::Full(value)),
+                FAIL => {
+                    match self.shared.try_send(&self.tx, value) {
+                        Ok(_) => Ok(StepResult::Continue),
+                        Err(e) => Err(e)
+                    }
+                },
+                _ => {
+                    if overflow.is_empty() {
+                        match self.shared.try_send(&self.tx, value) {
+                            Ok(_) => (),
+                            Err(TrySendError::Full(v)) => overflow.push_back(v),
+                            Err(e) => return Err(e)
+                        }
+                    } else {
+                        overflow.push_back(value);
+                    }
+                    Ok(StepResult::Continue)
+                }
+            }
+        }
+
+        fn complete(&mut self) -> Result<(), TrySendError<O>> {
+            Ok(())
+        }
+    }
+
+    impl<I, O, SR> BoundedTransducingSender<I, O, SR>
+        where SR: Reducing<I, (), TrySendError<O>> {
+
+        fn step(&mut self, f: I, mode: u8) -> Result<bool, TrySendError<O>> {
+            if self.stopped {
+                return Ok(false);
+            }
+            self.shared.mode.store(mode, Ordering::Relaxed);
+            match self.rf.step(f) {
+                Ok(StepResult::Continue) => Ok(true),
+                Ok(StepResult::Stop) => {
+                    self.stopped = true;
+                    Ok(false)
+                },
+                Err(e) => Err(e)
+            }
+        }
+
+        fn complete(&mut self, mode: u8) -> Result<(), TrySendError<O>> {
+            if self.completed {
+                return Ok(());
+            }
+            self.completed = true;
+            self.shared.mode.store(mode, Ordering::Relaxed);
+            self.rf.complete()
+        }
+
+        /// Blocks while the channel is full.  Should a reducing function
+        /// further down fail with `TrySendError::Full` regardless, the value
+        /// is returned as a `SendError`
+        pub fn send(&mut self, f: I) -> Result<bool, SendError<O>> {
+            match self.step(f, BLOCK) {
+                Ok(sent) => Ok(sent),
+                Err(TrySendError::Disconnected(v)) | Err(TrySendError::Full(v)) => Err(SendError(v))
+            }
+        }
+
+        /// Fails with `TrySendError::Full` rather than blocking.  Where the
+        /// transducer produces several values from one input, those before
+        /// the failing one will already have been sent.
+        pub fn try_send(&mut self, f: I) -> Result<bool, TrySendError<O>> {
+            self.step(f, FAIL)
+        }
+
+        /// Completes the transducer, flushing any values it holds.  Closing
+        /// more than once completes it once.
+        pub fn close(&mut self) -> Result<(), SendError<O>> {
+            match self.complete(BLOCK) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Disconnected(v)) | Err(TrySendError::Full(v)) => Err(SendError(v))
+            }
+        }
+    }
+
+    #[cfg(feature = "futures-sink")]
+    impl<I, O, SR> BoundedTransducingSender<I, O, SR>
+        where SR: Reducing<I, (), TrySendError<O>> {
+
+        /// Steps `f` through the transducer without blocking, holding back
+        /// any values the channel has no room for
+        pub(super) fn hold_send(&mut self, f: I) -> Result<(), SendError<O>> {
+            match self.step(f, HOLD) {
+                Ok(_) => Ok(()),
+                Err(TrySendError::Disconnected(v)) | Err(TrySendError::Full(v)) => Err(SendError(v))
+            }
+        }
+
+        /// As `hold_send`, but completes the transducer
+        pub(super) fn hold_close(&mut self) -> Result<(), SendError<O>> {
+            match self.complete(HOLD) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Disconnected(v)) | Err(TrySendError::Full(v)) => Err(SendError(v))
+            }
+        }
+
+        /// Sends the values held back, returning `Pending` while the channel
+        /// is full.  The waker is registered before trying, so the receiver
+        /// making room in between wakes it rather than being missed.
+        pub(super) fn poll_overflow(&mut self, cx: &mut Context) -> Poll<Result<(), SendError<O>>> {
+            *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+            let mut overflow = self.shared.overflow.lock().unwrap();
+            while let Some(value) = overflow.pop_front() {
+                match self.shared.try_send(&self.tx, value) {
+                    Ok(_) => (),
+                    Err(TrySendError::Full(v)) => {
+                        overflow.push_front(v);
+                        return Poll::Pending;
+                    },
+                    Err(TrySendError::Disconnected(v)) => return Poll::Ready(Err(SendError(v)))
+                }
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// The receiving end of a `bounded_transducing_channel`, which lets a
+    /// sender waiting for room know as values are received.  As an
+    /// iterator, it blocks for each value and ends once the sender has been
+    /// dropped.
+    pub struct BoundedReceiver<O> {
+        rx: Receiver<O>,
+        shared: Arc<BoundedState<O>>
+    }
+
+    impl<O> BoundedReceiver<O> {
+        fn received<T>(&self, value: T) -> T {
+            self.shared.received();
+            value
+        }
+
+        pub fn recv(&self) -> Result<O, RecvError> {
+            self.rx.recv().map(|value| self.received(value))
+        }
+
+        pub fn try_recv(&self) -> Result<O, TryRecvError> {
+            self.rx.try_recv().map(|value| self.received(value))
+        }
+
+        pub fn recv_timeout(&self, timeout: Duration) -> Result<O, RecvTimeoutError> {
+            self.rx.recv_timeout(timeout).map(|value| self.received(value))
+        }
+    }
+
+    impl<O> Iterator for BoundedReceiver<O> {
+        type Item = O;
+
+        fn next(&mut self) -> Option<O> {
+            self.recv().ok()
+        }
+    }
+
+    pub fn bounded_transducing_channel<I, O, T, RO>(capacity: usize, transducer: T)
+                                                    -> (BoundedTransducingSender<I, O, RO>,
+                                                        BoundedReceiver<O>)
+        where RO: Reducing<I, (), TrySendError<O>>,
+              T: Transducer<SyncSenderReducer<O>, RO=RO> {
+        bounded(capacity, transducer, BoundedState {
+            mode: AtomicU8::new(BLOCK),
+            overflow: Mutex::new(VecDeque::new()),
+            waker: Mutex::new(None),
+            #[cfg(feature = "metrics")]
+            depth: None
+        })
+    }
+
+    /// As `bounded_transducing_channel`, but tracks the depth of the queue,
+    /// the number of values sent and not yet received, in the gauge `name`.
+    /// A value is counted as soon as `send` starts sending it, so while
+    /// `send` blocks the depth is one more than the capacity.  The gauge is
+    /// registered with the recorder installed when the channel is created.
+    #[cfg(feature = "metrics")]
+    pub fn metrics_gauge<I, O, T, RO>(name: &'static str, capacity: usize, transducer: T)
+                                      -> (BoundedTransducingSender<I, O, RO>,
+                                          BoundedReceiver<O>)
+        where RO: Reducing<I, (), TrySendError<O>>,
+              T: Transducer<SyncSenderReducer<O>, RO=RO> {
+        bounded(capacity, transducer, BoundedState {
+            mode: AtomicU8::new(BLOCK),
+            overflow: Mutex::new(VecDeque::new()),
+            waker: Mutex::new(None),
+            depth: Some(gauge!(name))
+        })
+    }
+
+    fn bounded<I, O, T, RO>(capacity: usize, transducer: T, state: BoundedState<O>)
+                            -> (BoundedTransducingSender<I, O, RO>, BoundedReceiver<O>)
+        where RO: Reducing<I, (), TrySendError<O>>,
+              T: Transducer<SyncSenderReducer<O>, RO=RO> {
+        let (tx, rx) = sync_channel(capacity);
+        let shared = Arc::new(state);
+        let sender = BoundedTransducingSender {
+            rf: transducer.new(SyncSenderReducer {
+                #[cfg(feature = "futures-sink")]
+                tx: tx.clone(),
+                #[cfg(not(feature = "futures-sink"))]
+                tx,
+                shared: shared.clone()
+            }),
+            shared: shared.clone(),
+            #[cfg(feature = "futures-sink")]
+            tx,
+            stopped: false,
+            completed: false,
+            i_type: PhantomData,
+            o_type: PhantomData
+        };
+        (sender, BoundedReceiver {
+            rx,
+            shared
+        })
+    }
+
+    struct SharedState<R> {
+        rf: R,
+        senders: usize,
+        completed: bool
+    }
+
+    /// A reducing function shared between several senders, the underlying
+    /// reducing function is only completed once every sender has closed.
+    /// Closing a sender more than once counts once, and once completed any
+    /// further values stop the sender rather than reaching the reducing
+    /// function.
+    pub struct SharedReducer<R> {
+        shared: Arc<Mutex<SharedState<R>>>,
+        closed: bool
+    }
+
+    impl<I, R, E> Reducing<I, (), E> for SharedReducer<R>
+        where R: Reducing<I, (), E> {
 
-    use ::{Transducer, Reducing, StepResult};
+        type Item = R::Item;
 
-    pub trait Ref {
-        type Input;
+        #[inline]
+        fn step(&mut self, value: I) -> Result<StepResult, E> {
+            let mut state = self.shared.lock().unwrap();
+            if state.completed {
+                return Ok(StepResult::Stop);
+            }
+            state.rf.step(value)
+        }
 
-        fn transduce_ref<'a, T, O, RO, E>(&'a self, transducer: T) -> Result<Vec<O>, E>
-            where RO: Reducing<&'a Self::Input, Vec<O>, E>,
-                  T: Transducer<VecReducer<O>, RO=RO>;
+        fn complete(&mut self) -> Result<(), E> {
+            if self.closed {
+                return Ok(());
+            }
+            self.closed = true;
+            let mut state = self.shared.lock().unwrap();
+            state.senders -= 1;
+            if state.senders == 0 && !state.completed {
+                state.completed = true;
+                state.rf.complete()
+            } else {
+                Ok(())
+            }
+        }
     }
 
-    pub trait Into {
+    pub struct TransducingSenderFactory<I, O, SR> {
+        shared: Arc<Mutex<SharedState<SR>>>,
+        i_type: PhantomData<I>,
+        o_type: PhantomData<O>
+    }
+
+    impl<I, O, SR> TransducingSenderFactory<I, O, SR>
+        where SR: Reducing<I, (), SendError<O>> {
+
+        /// Each sender must be closed, the transducer is completed when the
+        /// last one is
+        pub fn new_sender(&self) -> TransducingSender<I, O, SharedReducer<SR>> {
+            self.shared.lock().unwrap().senders += 1;
+            TransducingSender {
+                rf: SharedReducer {
+                    shared: self.shared.clone(),
+                    closed: false
+                },
+                stopped: false,
+                i_type: PhantomData,
+                o_type: PhantomData
+            }
+        }
+    }
+
+    pub fn multi_producer_transducing_channel<I, O, T, RO>(transducer: T)
+                                                           -> (TransducingSenderFactory<I, O, RO>,
+                                                               Receiver<O>)
+        where RO: Reducing<I, (), SendError<O>> + Send,
+              T: Transducer<SenderReducer<O>, RO=RO> {
+        let (tx, rx) = channel();
+        let factory = TransducingSenderFactory {
+            shared: Arc::new(Mutex::new(SharedState {
+                rf: transducer.new(SenderReducer(tx)),
+                senders: 0,
+                completed: false
+            })),
+            i_type: PhantomData,
+            o_type: PhantomData
+        };
+        (factory, rx)
+    }
+
+    pub trait ToSender {
         type Input;
 
-        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
-            where RO: Reducing<Self::Input, Vec<O>, E>,
-                  T: Transducer<VecReducer<O>, RO=RO>;
+        /// Sends each transduced value to `tx`, including any flushed when
+        /// the transducer completes, e.g. to feed a worker thread.  `tx` is
+        /// dropped on return, so if it was the only sender the receiver
+        /// then sees the channel disconnect.
+        fn transduce_to_sender<T, O, RO>(self, tx: Sender<O>, transducer: T) -> Result<(), SendError<O>>
+            where RO: Reducing<Self::Input, (), SendError<O>>,
+                  T: Transducer<SenderReducer<O>, RO=RO>;
     }
 
-    pub struct VecReducer<O>(Rc<RefCell<Vec<O>>>);
+    impl<X> ToSender for Vec<X> {
+        type Input = X;
 
-    impl<'a, O> Reducing<O, Vec<O>, ()> for VecReducer<O> {
-        type Item = O;
+        fn transduce_to_sender<T, O, RO>(self, tx: Sender<O>, transducer: T) -> Result<(), SendError<O>>
+            where RO: Reducing<Self::Input, (), SendError<O>>,
+                  T: Transducer<SenderReducer<O>, RO=RO> {
+            reduce(self, transducer.new(SenderReducer(tx)))
+        }
+    }
 
-        #[inline]
-        fn step(&mut self, value: O) -> Result<StepResult, ()> {
-            self.0.borrow_mut().push(value);
-            Ok(StepResult::Continue)
+    pub trait CollectReceiver<O> {
+        /// Blocks until every sender has been dropped, returning
+        /// all values received in order, including those flushed when the
+        /// transducer completed
+        fn into_vec(self) -> Vec<O>;
+    }
+
+    impl<O> CollectReceiver<O> for Receiver<O> {
+        fn into_vec(self) -> Vec<O> {
+            self.into_iter().collect()
         }
+    }
 
-        fn complete(&mut self) -> Result<(), ()> {
-            Ok(())
+    impl<O> CollectReceiver<O> for BoundedReceiver<O> {
+        fn into_vec(self) -> Vec<O> {
+            self.collect()
         }
     }
 
-    impl<X> Ref for Vec<X> {
-        type Input = X;
+    /// Yields values from whichever receiver has one ready, polling each in
+    /// turn.  Ends once every receiver has disconnected.
+    pub struct SelectIterator<O> {
+        receivers: Vec<Receiver<O>>,
+        next: usize
+    }
 
-        fn transduce_ref<'a, T, O, RO, E>(&'a self, transducer: T) -> Result<Vec<O>, E>
-            where RO: Reducing<&'a Self::Input, Vec<O>, E>,
-                  T: Transducer<VecReducer<O>, RO=RO> {
-            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
-            {
-                let rr = VecReducer(res.clone());
-                let mut reducing = transducer.new(rr);
-                reducing.init();
-                for val in self.iter() {
-                    match reducing.step(val) {
-                        Ok(StepResult::Continue) => (),
-                        Ok(StepResult::Stop) => break,
-                        Err(e) => return Err(e)
+    impl<O> Iterator for SelectIterator<O> {
+        type Item = O;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                let mut polled = 0;
+                while polled < self.receivers.len() {
+                    if self.next >= self.receivers.len() {
+                        self.next = 0;
+                    }
+                    match self.receivers[self.next].try_recv() {
+                        Ok(value) => {
+                            self.next += 1;
+                            return Some(value)
+                        },
+                        Err(TryRecvError::Empty) => {
+                            self.next += 1;
+                            polled += 1;
+                        },
+                        Err(TryRecvError::Disconnected) => {
+                            self.receivers.remove(self.next);
+                        }
                     }
                 }
-                try!(reducing.complete())
+                if self.receivers.is_empty() {
+                    return None
+                }
+                thread::yield_now();
             }
-            Ok(match Rc::try_unwrap(res) {
-                Ok(res) => res.into_inner(),
-                Err(_) => panic!("Other refs")
-            })
         }
     }
 
-    impl<X> Into for Vec<X> {
-        type Input = X;
+    pub fn select_transducing_channels<O>(receivers: Vec<Receiver<O>>) -> SelectIterator<O> {
+        SelectIterator {
+            receivers,
+            next: 0
+        }
+    }
 
-        fn transduce_into<T, O, RO, E>(self, transducer: T) -> Result<Vec<O>, E>
-            where RO: Reducing<Self::Input, Vec<O>, E>,
-                  T: Transducer<VecReducer<O>, RO=RO> {
-            let res = Rc::new(RefCell::new(Vec::with_capacity(self.len())));
-            {
-                let rr = VecReducer(res.clone());
-                let mut reducing = transducer.new(rr);
-                reducing.init();
-                for val in self.into_iter() {
-                    match reducing.step(val) {
-                        Ok(StepResult::Continue) => (),
-                        Ok(StepResult::Stop) => break,
-                        Err(e) => return Err(e)
-                    }
-                }
-                try!(reducing.complete())
+    /// Fans several channels in to a single transducer, stepping values
+    /// through it from whichever receiver has one ready, as
+    /// `select_transducing_channels` does.  The transducer sees values in
+    /// the order they are received, so its state spans every channel, and
+    /// it completes once they have all disconnected.
+    pub fn transducing_select<I, O, T, RO, E>(receivers: Vec<Receiver<I>>, transducer: T)
+                                              -> TransduceIterator<SelectIterator<I>, O, RO>
+        where RO: Reducing<I, (), E>,
+              T: Transducer<IterReducer<O>, RO=RO> {
+        select_transducing_channels(receivers).transduce(transducer)
+    }
+
+    /// Receives the values of a `latency_transducing_channel`, each paired
+    /// with the time it spent in the channel
+    pub struct LatencyReceiver<O>(Receiver<(Instant, O)>);
+
+    impl<O> LatencyReceiver<O> {
+        pub fn recv(&self) -> Result<(Duration, O), RecvError> {
+            self.0.recv().map(|(sent, value)| (sent.elapsed(), value))
+        }
+
+        pub fn try_recv(&self) -> Result<(Duration, O), TryRecvError> {
+            self.0.try_recv().map(|(sent, value)| (sent.elapsed(), value))
+        }
+    }
+
+    /// As `transducing_channel`, but each value is stamped as it is sent and
+    /// received along with the time between the two, for profiling latency
+    pub fn latency_transducing_channel<I, O, T, RO>(transducer: T)
+                                                    -> (TransducingSender<I, (Instant, O), RO>,
+                                                        LatencyReceiver<O>)
+        where RO: Reducing<I, (), SendError<(Instant, O)>>,
+              T: Transducer<TimestampReducer<SenderReducer<(Instant, O)>>, RO=RO> {
+        let (tx, rx) = channel();
+        let sender = TransducingSender {
+            rf: transducer.new(timestamp().new(SenderReducer(tx))),
+            stopped: false,
+            i_type: PhantomData,
+            o_type: PhantomData
+        };
+        (sender, LatencyReceiver(rx))
+    }
+
+    /// Returned by a `TimeoutReceiver` when no value arrived in time
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct TimeoutError {
+        pub timeout: Duration
+    }
+
+    impl fmt::Display for TimeoutError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "no value received within {:?}", self.timeout)
+        }
+    }
+
+    impl Error for TimeoutError {}
+
+    /// Yields the values of a receiver, or a `TimeoutError` whenever the
+    /// next value takes longer than the timeout to arrive.  Ends once every
+    /// sender has disconnected.
+    pub struct TimeoutReceiver<O> {
+        receiver: Receiver<O>,
+        timeout: Duration
+    }
+
+    impl<O> Iterator for TimeoutReceiver<O> {
+        type Item = Result<O, TimeoutError>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match self.receiver.recv_timeout(self.timeout) {
+                Ok(value) => Some(Ok(value)),
+                Err(RecvTimeoutError::Timeout) => Some(Err(TimeoutError {
+                    timeout: self.timeout
+                })),
+                Err(RecvTimeoutError::Disconnected) => None
             }
-            Ok(match Rc::try_unwrap(res) {
-                Ok(res) => res.into_inner(),
-                Err(_) => panic!("Other refs")
-            })
+        }
+    }
+
+    /// Waits at most `timeout` for each value, so that a stalled source
+    /// fails rather than blocking forever.  Collecting into a
+    /// `Result<Vec<O>, TimeoutError>` stops at the first timeout.
+    pub fn with_timeout<O>(receiver: Receiver<O>, timeout: Duration) -> TimeoutReceiver<O> {
+        TimeoutReceiver {
+            receiver,
+            timeout
         }
     }
 }
 
-pub mod iter {
-    use std::cell::RefCell;
-    use std::collections::VecDeque;
-    use std::rc::Rc;
+/// Implements `futures_sink::Sink` for `TransducingSender` and
+/// `BoundedTransducingSender`, so a transducing channel can be fed by async
+/// streams, e.g. with `SinkExt::send_all`
+#[cfg(feature = "futures-sink")]
+pub mod futures_sink {
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    use std::sync::mpsc::{SendError, TrySendError};
 
-    use ::{Transducer, Reducing, StepResult};
+    use ::futures_sink::Sink;
 
-    pub trait TransduceIter {
-        type UnderlyingIterator;
-        type Item;
+    use ::Reducing;
+    use super::channels::{BoundedTransducingSender, TransducingSender};
 
-        fn transduce<T, O, RO, E>(self, transducer: T) -> TransduceIterator<Self::UnderlyingIterator, O, RO>
-            where RO: Reducing<Self::Item, (), E>,
-                  T: Transducer<IterReducer<O>, RO=RO>;
+    /// The channel is unbounded, so the sender is always ready.  Each item
+    /// is stepped through the transducer by `start_send`, and closing the
+    /// sink completes the transducer, flushing any values it holds.  Items
+    /// sent after the transducer has stopped are dropped.
+    impl<I, O, SR> Sink<I> for TransducingSender<I, O, SR>
+        where SR: Reducing<I, (), SendError<O>>,
+              Self: Unpin {
+
+        type Error = SendError<O>;
+
+        fn poll_ready(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
+            self.get_mut().send(item).map(|_| ())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(self.get_mut().close())
+        }
     }
 
-    impl<I, T> TransduceIter for I
-        where I: Iterator<Item=T> {
+    /// The sender is ready once every value the channel had no room for has
+    /// been sent, and `Pending` until the receiver makes room for them.
+    /// `start_send` never blocks: values the transducer produces while the
+    /// channel is full are held back until then.  Closing the sink completes
+    /// the transducer and waits for the values it flushes to be sent.
+    impl<I, O, SR> Sink<I> for BoundedTransducingSender<I, O, SR>
+        where SR: Reducing<I, (), TrySendError<O>>,
+              Self: Unpin {
 
-        type UnderlyingIterator = I;
-        type Item = T;
+        type Error = SendError<O>;
 
-        fn transduce<TR, O, RO, E>(self, transducer: TR) -> TransduceIterator<Self::UnderlyingIterator, O, RO>
-            where RO: Reducing<Self::Item, (), E>,
-                  TR: Transducer<IterReducer<O>, RO=RO> {
-            let buffer = Rc::new(RefCell::new(VecDeque::new()));
+        fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.get_mut().poll_overflow(cx)
+        }
 
-            TransduceIterator {
-                underlying: self,
-                buffer: buffer.clone(),
-                rf: transducer.new(IterReducer(buffer.clone())),
-                runoff: false
+        fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
+            self.get_mut().hold_send(item)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.get_mut().poll_overflow(cx)
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            let sender = self.get_mut();
+            match sender.poll_overflow(cx) {
+                Poll::Ready(Ok(())) => (),
+                other => return other
+            }
+            if let Err(e) = sender.hold_close() {
+                return Poll::Ready(Err(e));
             }
+            sender.poll_overflow(cx)
         }
     }
+}
 
-    pub struct IterReducer<T>(Rc<RefCell<VecDeque<T>>>);
+/// A transducing channel whose receiving end is a `futures_core::Stream`,
+/// so transduced values can be consumed by async code, e.g. with
+/// `StreamExt::collect`
+#[cfg(feature = "futures-stream")]
+pub mod futures_stream {
+    use alloc::collections::VecDeque;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+    use std::sync::{Arc, Mutex};
+    use std::sync::mpsc::SendError;
 
-    impl<T> Reducing<T, (), ()> for IterReducer<T> {
-        type Item = T;
+    use ::futures_core::Stream;
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::channels::TransducingSender;
+
+    struct StreamState<O> {
+        values: VecDeque<O>,
+        waker: Option<Waker>,
+        sender_dropped: bool,
+        stream_dropped: bool
+    }
+
+    /// Queues values for a `TransducingStream`, waking it as they arrive.
+    /// Fails once the stream has been dropped.
+    pub struct StreamReducer<O>(Arc<Mutex<StreamState<O>>>);
+
+    impl<O> Reducing<O, (), SendError<O>> for StreamReducer<O> {
+        type Item = O;
 
         #[inline]
-        fn step(&mut self, value: T) -> Result<StepResult, ()> {
-            self.0.borrow_mut().push_back(value);
+        fn step(&mut self, value: O) -> Result<StepResult, SendError<O>> {
+            let mut state = self.0.lock().unwrap();
+            if state.stream_dropped {
+                return Err(SendError(value));
+            }
+            state.values.push_back(value);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
             Ok(StepResult::Continue)
         }
 
-        fn complete(&mut self) -> Result<(), ()> {
+        fn complete(&mut self) -> Result<(), SendError<O>> {
             Ok(())
         }
     }
 
-    pub struct TransduceIterator<I, O, RF> {
-        underlying: I,
-        buffer: Rc<RefCell<VecDeque<O>>>,
-        rf: RF,
-        runoff: bool
+    impl<O> Drop for StreamReducer<O> {
+        fn drop(&mut self) {
+            let mut state = self.0.lock().unwrap();
+            state.sender_dropped = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
     }
 
-    impl<I, IN, O, RF> Iterator for TransduceIterator<I, O, RF>
-        where I: Iterator<Item=IN>,
-              RF: Reducing<IN, (), ()> {
+    /// Yields the values sent through the transducer, ending once the
+    /// sender has been dropped and every value has been yielded
+    pub struct TransducingStream<O>(Arc<Mutex<StreamState<O>>>);
 
+    impl<O> Stream for TransducingStream<O> {
         type Item = O;
 
-        #[inline]
-        fn next(&mut self) -> Option<Self::Item> {
-            loop {
-                if !self.runoff && self.buffer.borrow().is_empty() {
-                    match self.underlying.next() {
-                        None => {
-                            self.runoff = true;
-                            self.rf.complete().unwrap();
-                        },
-                        Some(value) => {
-                            match self.rf.step(value) {
-                                Ok(StepResult::Continue) => (),
-                                Ok(StepResult::Stop) => {
-                                    self.runoff = true;
-                                    self.rf.complete().unwrap();
-                                },
-                                Err(_) => unreachable!()
-                            }
-                        }
-                    }
-                }
-                if self.runoff && self.buffer.borrow().is_empty() {
-                    return None
-                }
-                match self.buffer.borrow_mut().pop_front() {
-                    None => (),
-                    Some(value) => return Some(value)
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<O>> {
+            let mut state = self.0.lock().unwrap();
+            match state.values.pop_front() {
+                Some(value) => Poll::Ready(Some(value)),
+                None if state.sender_dropped => Poll::Ready(None),
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
                 }
             }
         }
     }
-}
 
-pub mod channels {
-    use std::marker::PhantomData;
-    use std::sync::mpsc::{Receiver, Sender, SendError, channel};
+    impl<O> Drop for TransducingStream<O> {
+        fn drop(&mut self) {
+            self.0.lock().unwrap().stream_dropped = true;
+        }
+    }
 
-    use ::{Transducer, Reducing, StepResult};
+    /// As `transducing_channel`, but received through a `Stream`
+    pub fn transducing_stream<I, O, T, RO>(transducer: T) -> (TransducingSender<I, O, RO>,
+                                                             TransducingStream<O>)
+        where RO: Reducing<I, (), SendError<O>>,
+              T: Transducer<StreamReducer<O>, RO=RO> {
+        let state = Arc::new(Mutex::new(StreamState {
+            values: VecDeque::new(),
+            waker: None,
+            sender_dropped: false,
+            stream_dropped: false
+        }));
+        let sender = TransducingSender::from_reducer(transducer.new(StreamReducer(state.clone())));
+        (sender, TransducingStream(state))
+    }
+}
 
-    pub struct TransducingSender<O, SR>
-        where SR: Reducing<O, (), SendError<O>> {
+/// Transducing channels backed by `crossbeam-channel`, whose receivers can
+/// be selected over without polling
+#[cfg(feature = "crossbeam")]
+pub mod crossbeam_channels {
+    use alloc::vec::Vec;
+    use std::sync::mpsc::SendError;
 
-        rf: SR,
-        o_type: PhantomData<O>
-    }
+    use ::crossbeam_channel::{unbounded, Receiver, Select, Sender};
+
+    use ::{Transducer, Reducing, StepResult};
+    use super::channels::TransducingSender;
 
     pub struct SenderReducer<T>(Sender<T>);
 
@@ -221,7 +3021,7 @@ pub mod channels {
         fn step(&mut self, value: O) -> Result<StepResult, SendError<O>> {
             match self.0.send(value) {
                 Ok(_) => Ok(StepResult::Continue),
-                Err(e) => Err(e)
+                Err(e) => Err(SendError(e.into_inner()))
             }
         }
 
@@ -230,31 +3030,137 @@ pub mod channels {
         }
     }
 
-    impl<O, SR> TransducingSender<O, SR>
-        where SR: Reducing<O, (), SendError<O>> {
+    /// As `channels::transducing_channel`, but with a `crossbeam-channel`
+    /// `Receiver`
+    pub fn transducing_channel<I, O, T, RO>(transducer: T) -> (TransducingSender<I, O, RO>,
+                                                               Receiver<O>)
+        where RO: Reducing<I, (), SendError<O>>,
+              T: Transducer<SenderReducer<O>, RO=RO> {
+        let (tx, rx) = unbounded();
+        (TransducingSender::from_reducer(transducer.new(SenderReducer(tx))), rx)
+    }
+
+    /// Yields values from whichever receiver has one ready, blocking until
+    /// one does.  Ends once every receiver has disconnected.
+    pub struct SelectIterator<O> {
+        receivers: Vec<Receiver<O>>
+    }
 
-        pub fn send(&mut self, f: O) -> Result<bool, SendError<O>> {
-            match self.rf.step(f) {
-                Ok(StepResult::Continue) => Ok(true),
-                Ok(StepResult::Stop) => Ok(false),
-                Err(e) => Err(e)
+    impl<O> Iterator for SelectIterator<O> {
+        type Item = O;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while !self.receivers.is_empty() {
+                let mut select = Select::new();
+                for rx in &self.receivers {
+                    select.recv(rx);
+                }
+                let op = select.select();
+                let index = op.index();
+                match op.recv(&self.receivers[index]) {
+                    Ok(value) => return Some(value),
+                    Err(_) => {
+                        self.receivers.remove(index);
+                    }
+                }
             }
+            None
         }
+    }
 
-        pub fn close(&mut self) -> Result<(), SendError<O>> {
-            self.rf.complete()
+    /// As `channels::select_transducing_channels`, but blocks on every
+    /// receiver at once rather than polling them in turn.  When several
+    /// have a value ready one is picked at random, so none is starved.
+    pub fn select_transducing_channels<O>(receivers: Vec<Receiver<O>>) -> SelectIterator<O> {
+        SelectIterator {
+            receivers
         }
     }
+}
 
-    pub fn transducing_channel<I, O, T, RO>(transducer: T) -> (TransducingSender<I, RO>,
-                                                               Receiver<O>)
-        where RO: Reducing<I, (), SendError<I>, Item=O>,
-              T: Transducer<SenderReducer<O>, RO=RO> {
-        let (tx, rx) = channel();
-        let sender = TransducingSender {
-            rf: transducer.new(SenderReducer(tx)),
-            o_type: PhantomData
-        };
-        (sender, rx)
+#[cfg(feature = "serde")]
+pub mod checkpoint {
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+    use serde_json::Error;
+
+    use ::{Reducing, StepResult};
+
+    /// Wraps a reducing function, snapshotting its state every `every`
+    /// steps.  Only reducing functions without closures, and whose
+    /// underlying reducing functions are likewise, can be serialized.
+    pub struct Checkpoint<R> {
+        inner: R,
+        every: usize,
+        steps: usize,
+        snapshot: Option<String>,
+        error: Option<Error>
+    }
+
+    impl<R> Checkpoint<R>
+        where R: Serialize {
+
+        pub fn new(every: usize, inner: R) -> Self {
+            assert!(every != 0, "checkpoint interval must be non-zero");
+            Checkpoint {
+                inner,
+                every,
+                steps: 0,
+                snapshot: None,
+                error: None
+            }
+        }
+
+        /// The most recent snapshot, if one has been taken
+        pub fn snapshot(&self) -> Option<&str> {
+            self.snapshot.as_ref().map(|s| &s[..])
+        }
+
+        /// The error from the most recent attempt at a snapshot, if it
+        /// failed.  The previous snapshot is kept in that case
+        pub fn snapshot_error(&self) -> Option<&Error> {
+            self.error.as_ref()
+        }
+
+        pub fn into_inner(self) -> R {
+            self.inner
+        }
+    }
+
+    impl<I, OF, E, R> Reducing<I, OF, E> for Checkpoint<R>
+        where R: Reducing<I, OF, E> + Serialize {
+
+        type Item = R::Item;
+
+        fn init(&mut self) {
+            self.inner.init();
+        }
+
+        #[inline]
+        fn step(&mut self, value: I) -> Result<StepResult, E> {
+            let result = self.inner.step(value)?;
+            self.steps += 1;
+            if self.steps == self.every {
+                self.steps = 0;
+                match serde_json::to_string(&self.inner) {
+                    Ok(snapshot) => {
+                        self.snapshot = Some(snapshot);
+                        self.error = None;
+                    },
+                    Err(e) => self.error = Some(e)
+                }
+            }
+            Ok(result)
+        }
+
+        fn complete(&mut self) -> Result<(), E> {
+            self.inner.complete()
+        }
+    }
+
+    /// Rebuilds a reducing function from a snapshot taken by a `Checkpoint`
+    pub fn restore_from_snapshot<R>(s: &str) -> Result<R, Error>
+        where R: DeserializeOwned {
+        serde_json::from_str(s)
     }
 }
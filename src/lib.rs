@@ -7,9 +7,43 @@
  * option. This file may not be copied, modified, or distributed
  * except according to those terms.
  */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "trace")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "metrics")]
+#[macro_use]
+extern crate metrics;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "futures-sink")]
+extern crate futures_sink;
+#[cfg(feature = "futures-stream")]
+extern crate futures_core;
+#[cfg(feature = "crossbeam")]
+extern crate crossbeam_channel;
+#[cfg(all(test, any(feature = "futures-sink", feature = "futures-stream")))]
+extern crate futures;
+#[cfg(all(test, feature = "metrics"))]
+extern crate metrics_util;
+#[cfg(test)]
+extern crate tokio;
+
+use alloc::boxed::Box;
+
 pub mod transducers;
 pub mod applications;
 
+pub use applications::{apply_to, apply_to_with, transduce, transduce_all, transduce_any, transduce_count,
+                       transduce_first, transduce_fold, transduce_last, transduce_max, transduce_min,
+                       transduce_product, transduce_sum, transduce_with};
+
 pub enum StepResult {
     Continue,
     Stop
@@ -28,18 +62,111 @@ pub trait Reducing<I, O, E> {
     /// TODO: the return type to contain an indicator of early termination
     fn step(&mut self, value: I) -> Result<StepResult, E>;
 
-    /// Transducers must call the underlying `complete`
+    /// Flushes any state held by the reducing function, e.g. a partially
+    /// filled partition, then calls the underlying `complete`.  Whoever
+    /// drives a reducing function must call this exactly once, after the
+    /// last `step`; dropping a reducing function without completing it
+    /// silently discards anything still buffered.
+    fn complete(&mut self) -> Result<(), E>;
+}
+
+/// A borrowed reducing function can be passed to a transducer, leaving it
+/// available to the caller once the transducer is done
+impl<I, O, E, R> Reducing<I, O, E> for &mut R
+    where R: Reducing<I, O, E> {
+
+    type Item = R::Item;
+
+    fn init(&mut self) {
+        (**self).init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        (**self).step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        (**self).complete()
+    }
+}
+
+/// The object safe part of `Reducing`, without the `Item` type, so that
+/// different reducing functions can be boxed as one type
+trait DynReducing<I, O, E> {
+    fn init(&mut self);
+
+    fn step(&mut self, value: I) -> Result<StepResult, E>;
+
     fn complete(&mut self) -> Result<(), E>;
 }
 
+impl<I, O, E, R> DynReducing<I, O, E> for R
+    where R: Reducing<I, O, E> {
+
+    fn init(&mut self) {
+        Reducing::init(self);
+    }
+
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        Reducing::step(self, value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        Reducing::complete(self)
+    }
+}
+
+/// A boxed reducing function which is `Send`, so that a whole transducing
+/// chain can be built on one thread and moved to another, e.g. a worker in
+/// a pool.  Any reducing function that is `Send` can be boxed; those of the
+/// provided transducers are `Send` when their functions and the reducing
+/// function beneath them are.
+pub struct BoxedSendReducing<I, O, E>(Box<dyn DynReducing<I, O, E> + Send>);
+
+impl<I, O, E> BoxedSendReducing<I, O, E> {
+    pub fn new<R>(reducing_fn: R) -> Self
+        where R: Reducing<I, O, E> + Send + 'static {
+
+        BoxedSendReducing(Box::new(reducing_fn))
+    }
+}
+
+impl<I, O, E> Reducing<I, O, E> for BoxedSendReducing<I, O, E> {
+    type Item = I;
+
+    fn init(&mut self) {
+        self.0.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.0.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.0.complete()
+    }
+}
+
 /// Defines a transducer that transforms a reducing function RI into
 /// a reducing function RO
 pub trait Transducer<RI> {
     type RO;
+    #[allow(clippy::wrong_self_convention)]
     fn new(self, reducing_fn: RI) -> Self::RO;
+
+    /// Applies the transducer to `reducing_fn`.  The same as `new`, but
+    /// clearer at the call site, so prefer this when applying transducers
+    fn apply(self, reducing_fn: RI) -> Self::RO
+        where Self: Sized {
+
+        self.new(reducing_fn)
+    }
 }
 
 /// Composed transducers
+#[must_use = "transducers do nothing unless applied"]
 pub struct ComposedTransducer<AT, BT> {
     a: AT,
     b: BT
@@ -51,32 +178,86 @@ impl <RI, RT, RO, AT, BT> Transducer<RI> for ComposedTransducer<AT, BT>
     type RO = RO;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        self.b.new(self.a.new(reducing_fn))
+        self.b.apply(self.a.apply(reducing_fn))
+    }
+}
+
+/// Transducers that turn each value into exactly one output, keeping no
+/// state between values, so that applications can call them directly
+/// rather than through a reducing function
+pub trait Stateless<I> {
+    type Output;
+
+    fn apply_one(&self, value: I) -> Self::Output;
+}
+
+impl<I, AT, BT> Stateless<I> for ComposedTransducer<AT, BT>
+    where BT: Stateless<I>,
+          AT: Stateless<BT::Output> {
+
+    type Output = AT::Output;
+
+    #[inline]
+    fn apply_one(&self, value: I) -> Self::Output {
+        self.a.apply_one(self.b.apply_one(value))
     }
 }
 
 pub fn compose<AT, BT>(a: AT, b: BT) -> ComposedTransducer<AT, BT> {
     ComposedTransducer {
-        a: a,
-        b: b
+        a,
+        b
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
-    use std::collections::HashMap;
+    use std::cell::{Cell, RefCell};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+    use std::collections::{BTreeMap, HashMap, LinkedList, VecDeque};
+    use std::io;
+    use std::mem;
+    use std::num::ParseIntError;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    #[cfg(feature = "trace")]
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(feature = "metrics")]
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+    use std::sync::mpsc::{self, TrySendError};
     use std::thread;
+    use std::time::Duration;
+
+    #[cfg(feature = "serde")]
+    use serde::{Serialize, Deserialize};
 
     use super::transducers;
-    use super::applications::vec::{Into, Ref};
-    use super::applications::iter::TransduceIter;
-    use super::applications::channels::transducing_channel;
+    use super::transducers::{BufferLimitExceeded, ValidationError, Validator, ZipLengthMismatch};
+    use super::{Reducing, StepResult, Transducer};
+    use super::applications::{drive, fn_reducer, transduce_tee};
+    use super::applications::vec::{Drain, Into, Ref, Sink, Unzip};
+    use super::applications::iter::{TransduceIter, TransduceResults};
+    use super::applications::map::Into as MapInto;
+    use super::applications::collections::Into as CollectionInto;
+    use super::applications::io::WriteLines;
+    use super::applications::string::TransduceStr;
+    use super::applications::async_iter::transduce_async;
+    use super::applications::channels::{transducing_channel, bounded_transducing_channel,
+                                        multi_producer_transducing_channel,
+                                        select_transducing_channels, transducing_select,
+                                        latency_transducing_channel, with_timeout,
+                                        CollectReceiver, TimeoutError, ToSender};
+    #[cfg(feature = "metrics")]
+    use super::applications::channels::metrics_gauge;
 
     #[test]
     fn test_vec_ref() {
         let source = vec![1, 2, 3];
         let transducer = transducers::map(|x| x + 1);
-        let result = source.transduce_ref(transducer).unwrap();
+        let result = source.transduce_ref::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![2, 3, 4], result);
     }
 
@@ -91,10 +272,254 @@ mod test {
         let ta = transducers::mapcat(duplicator);
         let tb = transducers::map(|x| x * 2);
         let transducer = super::compose(tb, ta);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![2, 2, 4, 4, 6, 6], result);
     }
 
+    #[test]
+    fn test_map_keys_values() {
+        {
+            let source = vec![(1, "a"), (2, "b")];
+            let transducer = transducers::map_values(|v: &str| v.to_uppercase());
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![(1, "A".to_string()), (2, "B".to_string())], result);
+        }
+        {
+            let source = vec![(1, "a"), (2, "b")];
+            let transducer = transducers::map_keys(|k| k * 10);
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![(10, "a"), (20, "b")], result);
+        }
+    }
+
+    #[test]
+    fn test_try_into() {
+        {
+            let source = vec!["1", "2", "3"];
+            let transducer = transducers::try_map(|s: &str| s.parse::<usize>());
+            let result = source.transduce_try_into::<ParseIntError, _, _, _>(transducer);
+            assert_eq!(Ok(vec![1, 2, 3]), result);
+        }
+        {
+            let source = vec!["1", "x", "3"];
+            let transducer = transducers::try_map(|s: &str| s.parse::<usize>());
+            let result = source.transduce_try_into::<ParseIntError, _, _, _>(transducer);
+            assert_eq!("invalid digit found in string", result.unwrap_err().to_string());
+        }
+    }
+
+    #[test]
+    fn test_apply_to() {
+        let result = super::apply_to(1..6, transducers::map(|x: usize| x * 2)).unwrap();
+        assert_eq!(vec![2, 4, 6, 8, 10], result);
+
+        let result = super::apply_to(vec!["a", "b", "c"], transducers::take(2)).unwrap();
+        assert_eq!(vec!["a", "b"], result);
+
+        let mut reducer = fn_reducer(0, |acc: &mut usize, x: usize| {
+            *acc += x;
+            Ok::<_, ()>(StepResult::Continue)
+        });
+        super::apply_to_with(1..6, transducers::filter(|x: &usize| x % 2 == 1), &mut reducer).unwrap();
+        assert_eq!(9, reducer.finish());
+    }
+
+    #[test]
+    fn test_transduce() {
+        let source = vec![1, 2, 3, 4, 5];
+        let expected: Vec<usize> = source.clone().into_iter().transduce(transducers::drop(2)).collect();
+        let result = super::transduce(source, transducers::drop(2)).unwrap();
+        assert_eq!(expected, result);
+
+        let mut reducer = fn_reducer(Vec::new(), |acc: &mut Vec<String>, x: String| {
+            acc.push(x);
+            Ok::<_, ()>(StepResult::Continue)
+        });
+        super::transduce_with("a b".chars(), transducers::map(|c: char| c.to_string()), &mut reducer).unwrap();
+        assert_eq!(vec!["a", " ", "b"], reducer.finish());
+    }
+
+    #[test]
+    fn test_transducer_apply() {
+        let mut reducer = fn_reducer(Vec::new(), |acc: &mut Vec<usize>, x: usize| {
+            acc.push(x);
+            Ok::<_, ()>(StepResult::Continue)
+        });
+        {
+            let transducer = super::compose(transducers::map(|x: usize| x + 1), transducers::take(2));
+            let mut rf = transducer.apply(&mut reducer);
+            for x in 0..5 {
+                if let StepResult::Stop = rf.step(x).unwrap() {
+                    break;
+                }
+            }
+            rf.complete().unwrap();
+        }
+        assert_eq!(vec![1, 2], reducer.finish());
+    }
+
+    #[test]
+    fn test_collection_sources() {
+        let deque: VecDeque<usize> = (1..7).collect();
+        let result = deque.transduce_into(transducers::filter(|x: &usize| *x > 3)).unwrap();
+        assert_eq!(vec![4, 5, 6], result);
+
+        let list: LinkedList<&str> = vec!["1", "2", "x"].into_iter().collect();
+        let result = list.transduce_try_into::<ParseIntError, _, _, _>(transducers::try_map(|s: &str| s.parse::<usize>()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_transduce_array() {
+        let result = [1, 2, 3, 4].transduce_into(transducers::filter(|x: &usize| *x > 2)).unwrap();
+        assert_eq!(vec![3, 4], result);
+
+        let transducer = super::compose(transducers::take(2), transducers::map(|s: String| s.len()));
+        let result = [String::from("a"), String::from("bb"), String::from("ccc")].transduce_into(transducer).unwrap();
+        assert_eq!(vec![1, 2], result);
+    }
+
+    /// Stays pending for `polls` polls before giving its value
+    struct Delay<T> {
+        polls: usize,
+        value: Option<T>
+    }
+
+    impl<T: Unpin> Future for Delay<T> {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+            if self.polls == 0 {
+                Poll::Ready(self.value.take().unwrap())
+            } else {
+                self.polls -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+                return value;
+            }
+        }
+    }
+
+    #[test]
+    fn test_transduce_async() {
+        let started = Cell::new(0);
+        let delayed = |x: usize| {
+            started.set(started.get() + 1);
+            Delay { polls: 5 - x, value: Some(x * 10) }
+        };
+        let mut future = Box::pin(transduce_async(1..5, |cx| {
+            super::compose(transducers::filter(|x: &usize| *x > 10), cx.async_map(delayed))
+        }));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(1, started.get());
+        let result = block_on(future).unwrap();
+        assert_eq!(vec![20, 30, 40], result);
+        assert_eq!(4, started.get());
+
+        let result = block_on(transduce_async(1..5, |cx| {
+            super::compose(transducers::take(2), cx.async_map(delayed))
+        })).unwrap();
+        assert_eq!(vec![10, 20], result);
+        assert_eq!(6, started.get());
+    }
+
+    #[test]
+    fn test_transduce_async_mid_pipeline() {
+        let delayed = |x: usize| Delay { polls: x % 3, value: Some(x * 10) };
+        let result = block_on(transduce_async(1..10, |cx| {
+            super::compose(transducers::take(3),
+                           super::compose(transducers::partition_all(2),
+                                          super::compose(cx.async_map(delayed),
+                                                         transducers::filter(|x: &usize| x % 2 == 1))))
+        })).unwrap();
+        assert_eq!(vec![vec![10, 30], vec![50, 70], vec![90]], result);
+
+        let result = block_on(transduce_async(vec![vec![1, 2], vec![3, 4, 5]], |cx| {
+            super::compose(transducers::take(4),
+                           super::compose(cx.async_map(|x: usize| Delay { polls: 1, value: Some(x) }),
+                                          transducers::mapcat(|xs: Vec<usize>| xs)))
+        })).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], result);
+    }
+
+    #[test]
+    fn test_transduce_async_tokio() {
+        let finished = Rc::new(RefCell::new(Vec::new()));
+        let sleep = |x: u64| {
+            let finished = finished.clone();
+            Sleep {
+                sleep: Box::pin(tokio::time::sleep(Duration::from_millis(20 - x * 4))),
+                finished,
+                value: Some(x)
+            }
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+        let result = runtime.block_on(transduce_async(1..5, |cx| {
+            super::compose(transducers::map(|x: u64| x * 2), cx.async_map(sleep))
+        })).unwrap();
+        assert_eq!(vec![2, 4, 6, 8], result);
+        assert_eq!(vec![1, 2, 3, 4], *finished.borrow());
+    }
+
+    /// Gives its value after a tokio sleep, recording when it does
+    struct Sleep {
+        sleep: Pin<Box<tokio::time::Sleep>>,
+        finished: Rc<RefCell<Vec<u64>>>,
+        value: Option<u64>
+    }
+
+    impl Future for Sleep {
+        type Output = u64;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<u64> {
+            match self.sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    let value = self.value.take().unwrap();
+                    self.finished.borrow_mut().push(value);
+                    Poll::Ready(value)
+                },
+                Poll::Pending => Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_boxed_send_reducing() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let sink = fn_reducer(tx, |tx: &mut std::sync::mpsc::Sender<usize>, x: usize| {
+            tx.send(x).unwrap();
+            Ok::<_, ()>(StepResult::Continue)
+        });
+        let transducer = super::compose(transducers::map(|xs: Vec<usize>| xs.iter().sum()),
+                                        super::compose(transducers::partition_all(2),
+                                                       transducers::map(|x: usize| x * 10)));
+        let mut reducer = super::BoxedSendReducing::new(transducer.apply(sink));
+        assert_send(&reducer);
+        thread::spawn(move || {
+            reducer.init();
+            for x in 1..6 {
+                reducer.step(x).unwrap();
+            }
+            reducer.complete().unwrap();
+        }).join().unwrap();
+        assert_eq!(vec![30, 70, 50], rx.into_iter().collect::<Vec<usize>>());
+
+        let (tx, _rx) = transducing_channel(transducers::map(|x: usize| x + 1));
+        assert_send(&tx);
+    }
+
     #[test]
     fn test_iterator() {
         let source = vec![1, 2, 3];
@@ -103,18 +528,121 @@ mod test {
         assert_eq!(vec![1, 1, 2, 2, 3, 3], result);
     }
 
+    #[test]
+    fn test_into_iter_transduced() {
+        let calls = Cell::new(0);
+        let source = vec![1, 2, 3, 4, 5];
+        let transducer = transducers::map(|x| {
+            calls.set(calls.get() + 1);
+            x * 2
+        });
+        let mut result = source.into_iter_transduced(transducer);
+        assert_eq!(0, calls.get());
+        assert_eq!(Some(2), result.next());
+        assert_eq!(Some(4), result.next());
+        assert_eq!(2, calls.get());
+    }
+
+    #[test]
+    fn test_transduce_lazy() {
+        let seen = Cell::new(0);
+        let transducer = transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x
+        });
+        let result: Vec<usize> = vec![1, 2, 3, 4, 5].transduce_lazy(transducer)
+            .map(|x| x * 10)
+            .take(2)
+            .collect();
+        assert_eq!(vec![10, 20], result);
+        assert_eq!(2, seen.get());
+    }
+
+    #[test]
+    fn test_iterator_sources() {
+        {
+            let transducer = super::compose(transducers::map(|x: usize| x * x), transducers::take(5));
+            let result:Vec<usize> = (0..1000).transduce(transducer).collect();
+            assert_eq!(vec![0, 1, 4, 9, 16], result);
+        }
+        {
+            let transducer = transducers::remove(|c: &char| c.is_whitespace());
+            let result:String = "a b c".chars().transduce(transducer).collect();
+            assert_eq!("abc", result);
+        }
+        {
+            let mut source = HashMap::new();
+            source.insert("a", 1);
+            source.insert("b", 2);
+            let transducer = transducers::map_values(|v: usize| v * 10);
+            let mut result:Vec<(&str, usize)> = source.into_iter().transduce(transducer).collect();
+            result.sort();
+            assert_eq!(vec![("a", 10), ("b", 20)], result);
+        }
+    }
+
+    #[test]
+    fn test_transduce_results() {
+        let seen = Cell::new(0);
+        let transducer = transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x * 2
+        });
+        let source: Vec<Result<usize, &str>> = vec![Ok(1), Ok(2), Err("bad line"), Ok(4)];
+        let result = source.into_iter().transduce_results(transducer);
+        assert_eq!(Err("bad line"), result);
+        assert_eq!(2, seen.get());
+
+        let source: Vec<io::Result<&str>> = vec![Ok("a"), Ok("b"), Ok("c")];
+        let result = source.into_iter().transduce_results(transducers::take(2)).unwrap();
+        assert_eq!(vec!["a", "b"], result);
+    }
+
+    #[test]
+    fn test_iterator_collect_results() {
+        let source = vec![1, 2, 3];
+        let expected = source.clone().transduce_into::<(), _, _, _>(transducers::map(|x: usize| x * 2)).unwrap();
+        let result = source.into_iter().transduce(transducers::map(|x: usize| Ok::<_, ()>(x * 2))).collect_results();
+        assert_eq!(Ok(expected), result);
+
+        let parse = || transducers::map(|s: &str| s.parse::<usize>());
+        let source = vec!["1", "x", "3", "y"];
+        assert!(source.clone().into_iter().transduce(parse()).collect_results().is_err());
+        assert_eq!(vec![1, 3], source.clone().into_iter().transduce(parse()).collect_ok());
+
+        let expected = source.clone().transduce_partition_results(parse()).unwrap();
+        let result = source.into_iter().transduce(parse()).collect_all();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_iterator_by_ref() {
+        let mut iter = vec![1, 2, 3, 4, 5, 6].into_iter().transduce(transducers::drop(1));
+        let first: Vec<usize> = iter.by_ref().take(2).collect();
+        assert_eq!(vec![2, 3], first);
+        let rest: Vec<usize> = iter.collect();
+        assert_eq!(vec![4, 5, 6], rest);
+
+        let mut iter = (1..8).transduce(transducers::partition_all(3));
+        let first: Vec<Vec<usize>> = iter.by_ref().take(1).collect();
+        assert_eq!(vec![vec![1, 2, 3]], first);
+        let rest: Vec<Vec<usize>> = iter.by_ref().collect();
+        assert_eq!(vec![vec![4, 5, 6], vec![7]], rest);
+        assert_eq!(None, iter.next());
+    }
+
     #[test]
     fn test_filter() {
         {
             let source = vec![1, 2, 3, 4, 5];
             let transducer = transducers::filter(|x| x % 2 == 0);
-            let result = source.transduce_into(transducer).unwrap();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
             assert_eq!(vec![2, 4], result);
         }
         {
             let source = vec![1, 2, 3, 4, 5];
             let transducer = transducers::remove(|x| x % 2 == 0);
-            let result = source.transduce_into(transducer).unwrap();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
             assert_eq!(vec![1, 3, 5], result);
         }
     }
@@ -124,38 +652,148 @@ mod test {
         {
             let source = vec![1, 2, 3, 4, 5, 6, 7];
             let transducer = transducers::partition(2);
-            let result = source.transduce_into(transducer).unwrap();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
             let expected_result:Vec<Vec<usize>> = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
             assert_eq!(expected_result, result);
         }
         {
             let source = vec![1, 2, 3, 4, 5, 6, 7];
             let transducer = transducers::partition_all(2);
-            let result = source.transduce_into(transducer).unwrap();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
             let expected_result:Vec<Vec<usize>> = vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7]];
             assert_eq!(expected_result, result);
         }
+        {
+            let source = vec![1, 2, 3, 4, 5, 6];
+            let transducer = transducers::partition_all(2);
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            let expected_result:Vec<Vec<usize>> = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+            assert_eq!(expected_result, result);
+        }
+    }
+
+    #[test]
+    fn test_chunks_exact() {
+        let source = vec![1, 2, 3, 4, 5, 6, 7];
+        let (transducer, remainder) = transducers::chunks_exact_with_remainder(3);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        let expected_result:Vec<Vec<usize>> = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(expected_result, result);
+        assert_eq!(vec![7], remainder.take());
+        assert!(remainder.take().is_empty());
+
+        fn is_send<T: Send>(_: &T) {}
+        is_send(&transducers::partition::<usize>(2));
+        is_send(&transducers::chunks_exact::<usize>(2));
+    }
+
+    #[test]
+    fn test_chunk_with_overlap() {
+        let source: Vec<usize> = (1..=6).collect();
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::chunk_with_overlap(3, 1)).unwrap();
+        assert_eq!(vec![vec![1, 2, 3], vec![3, 4, 5]], result);
+
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::chunk_with_overlap(2, 0)).unwrap();
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5, 6]], result);
+
+        let result = source.transduce_into::<(), _, _, _>(transducers::chunk_with_overlap(4, 3)).unwrap();
+        assert_eq!(vec![vec![1, 2, 3, 4], vec![2, 3, 4, 5], vec![3, 4, 5, 6]], result);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunk_with_overlap_too_large() {
+        let _ = transducers::chunk_with_overlap::<usize>(2, 2);
+    }
+
+    #[test]
+    fn test_windowed() {
+        let transducer = transducers::windowed(2, |w: &[i32]| w.iter().sum::<i32>());
+        let result = vec![1, 2, 3, 4].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![3, 5, 7], result);
+
+        let transducer = transducers::windowed(3, |w: &[i32]| w.iter().sum::<i32>());
+        let result = vec![1, 2].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_partition_into_n() {
+        let source: Vec<usize> = (1..=9).collect();
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::partition_into_n(3)).unwrap();
+        let expected_result = vec![(0, 1), (1, 2), (2, 3), (0, 4), (1, 5), (2, 6), (0, 7), (1, 8), (2, 9)];
+        assert_eq!(expected_result, result);
+
+        let transducer = transducers::partition_into_n_buffered(3);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        let expected_result = vec![vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]];
+        assert_eq!(expected_result, result);
     }
 
     #[test]
     fn test_take() {
         let source = vec![1, 2, 3, 4, 5, 6, 7];
         let transducer = transducers::take(5);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![1, 2, 3, 4, 5], result);
 
         let source2 = vec![1, 2, 3, 4, 5, 6, 7];
         let transducer2 = super::compose(transducers::take(2),
                                          transducers::filter(|x| x % 2 == 0));
-        let result = source2.transduce_into(transducer2).unwrap();
+        let result = source2.transduce_into::<(), _, _, _>(transducer2).unwrap();
         assert_eq!(vec![2, 4], result);
     }
 
+    #[test]
+    fn test_step_by() {
+        let source: Vec<usize> = (1..=10).collect();
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::every_nth(3)).unwrap();
+        assert_eq!(vec![3, 6, 9], result);
+
+        let result = source.transduce_into::<(), _, _, _>(transducers::step_by(3)).unwrap();
+        assert_eq!(vec![1, 4, 7, 10], result);
+    }
+
+    #[test]
+    fn test_sample_every() {
+        let source: Vec<usize> = (0..10).collect();
+        let (sample, _) = transducers::sample_every(2);
+        let result = source.clone().transduce_into::<(), _, _, _>(sample).unwrap();
+        assert_eq!(source.clone().transduce_into::<(), _, _, _>(transducers::step_by(3)).unwrap(), result);
+        assert_eq!(vec![0, 3, 6, 9], result);
+
+        let (sample, reset) = transducers::sample_every(2);
+        let transducer = super::compose(sample, transducers::map(|x: usize| {
+            if x == 4 {
+                reset.reset();
+            }
+            x
+        }));
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![0, 3, 4, 7], result);
+    }
+
+    #[test]
+    fn test_nth() {
+        let seen = Cell::new(0);
+        let source = vec![1, 2, 3, 4, 5, 6];
+        let transducer = super::compose(transducers::nth(2), transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x
+        }));
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![3], result);
+        assert_eq!(3, seen.get());
+
+        let result = vec![1, 2].transduce_into::<(), _, _, _>(transducers::nth(2)).unwrap();
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn test_drop() {
         let source = vec![1, 2, 3, 4, 5, 6, 7];
         let transducer = transducers::drop(2);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![3, 4, 5, 6, 7], result);
     }
 
@@ -163,15 +801,31 @@ mod test {
     fn test_take_while() {
         let source = vec![1, 2, 3, 4, 5, 6, 7];
         let transducer = transducers::take_while(|x| *x <= 5);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![1, 2, 3, 4, 5], result);
     }
 
+    #[test]
+    fn test_take_until() {
+        {
+            let source = vec![1, 2, 3, 4];
+            let transducer = transducers::take_until(|x| *x == 3);
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![1, 2, 3], result);
+        }
+        {
+            let source = vec![1, 2, 3, 4];
+            let transducer = transducers::take_until(|x| *x == 7);
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![1, 2, 3, 4], result);
+        }
+    }
+
     #[test]
     fn test_drop_while() {
         let source = vec![1, 2, 3, 4, 5, 6, 7];
         let transducer = transducers::drop_while(|x| *x <= 5);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![6, 7], result);
     }
 
@@ -182,7 +836,18 @@ mod test {
         replacements.insert(1, 4);
         replacements.insert(3, 7);
         let transducer = transducers::replace(replacements);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![4, 2, 7, 4, 2, 7, 4, 2, 7], result);
+    }
+
+    #[test]
+    fn test_replace_ordered() {
+        let source = vec![1, 2, 3, 1, 2, 3, 1, 2, 3];
+        let mut replacements = BTreeMap::new();
+        replacements.insert(1, 4);
+        replacements.insert(3, 7);
+        let transducer = transducers::replace_ordered(replacements);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         assert_eq!(vec![4, 2, 7, 4, 2, 7, 4, 2, 7], result);
     }
 
@@ -190,11 +855,36 @@ mod test {
     fn test_partition_by() {
         let source = vec![1, 2, 3, 4, 5, 6];
         let transducer = transducers::partition_by(|&x| x > 3);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result:Vec<Vec<usize>> = vec![vec![1, 2, 3], vec![4, 5, 6]];
         assert_eq!(expected_result, result);
     }
 
+    #[test]
+    fn test_group_adjacent_by() {
+        let source = vec![1, 2, 3, 4];
+        let transducer = transducers::group_adjacent_by(|x: &usize| x % 2);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![(1, vec![1]), (0, vec![2]), (1, vec![3]), (0, vec![4])], result);
+
+        let source = vec![1, 3, 5, 2, 4, 7];
+        let transducer = transducers::group_adjacent_by(|x: &usize| x % 2);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![(1, vec![1, 3, 5]), (0, vec![2, 4]), (1, vec![7])], result);
+
+        let empty: Vec<(usize, Vec<usize>)> = Vec::<usize>::new().transduce_into::<(), _, _, _>(transducers::group_adjacent_by(|x: &usize| x % 2)).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_partition_by_repeated_keys() {
+        let source = vec![1, 1, 2, 2, 2, 3];
+        let transducer = transducers::partition_by(|&x: &usize| x);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        let expected_result:Vec<Vec<usize>> = vec![vec![1, 1], vec![2, 2, 2], vec![3]];
+        assert_eq!(expected_result, result);
+    }
+
     #[test]
     fn test_keep() {
         let source = vec![1, 2, 3, 4, 5, 6];
@@ -205,7 +895,7 @@ mod test {
                 None
             }
         });
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result = vec![2, 4, 6];
         assert_eq!(expected_result, result);
     }
@@ -220,7 +910,7 @@ mod test {
                 None
             }
         });
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result = vec![6, 5, 4];
         assert_eq!(expected_result, result);
     }
@@ -229,7 +919,7 @@ mod test {
     fn test_map_indexed() {
         let source = vec![1, 2, 3, 4, 5, 6];
         let transducer = transducers::map_indexed(|idx, x| x + idx);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result = vec![1, 3, 5, 7, 9, 11];
         assert_eq!(expected_result, result);
     }
@@ -238,7 +928,7 @@ mod test {
     fn test_interpose() {
         let source = vec![1, 2, 3, 4];
         let transducer = transducers::interpose(0);
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result = vec![1, 0, 2, 0, 3, 0, 4];
         assert_eq!(expected_result, result);
     }
@@ -247,11 +937,37 @@ mod test {
     fn test_dedupe() {
         let source = vec![1, 2, 2, 2, 3, 3, 2, 3, 4];
         let transducer = transducers::dedupe();
-        let result = source.transduce_into(transducer).unwrap();
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
         let expected_result = vec![1, 2, 3, 2, 3, 4];
         assert_eq!(expected_result, result);
     }
 
+    #[test]
+    fn test_dedupe_with_count() {
+        let result = vec!["a", "a", "b"].transduce_into::<(), _, _, _>(transducers::dedupe_with_count()).unwrap();
+        assert_eq!(vec![("a", 2), ("b", 1)], result);
+
+        let result = vec![1, 2, 2, 2, 1, 1].transduce_into::<(), _, _, _>(transducers::dedupe_with_count()).unwrap();
+        assert_eq!(vec![(1, 1), (2, 3), (1, 2)], result);
+
+        // Not `Clone`, and equal by name only, so the id shows which value of
+        // each run was kept
+        #[derive(Debug)]
+        struct Token(&'static str, usize);
+        impl PartialEq for Token {
+            fn eq(&self, other: &Token) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Token {}
+
+        let source = vec![Token("x", 0), Token("x", 1), Token("x", 2), Token("y", 3)];
+        let transducer = super::compose(transducers::map(|(token, count): (Token, usize)| (token.0, token.1, count)),
+                                        transducers::dedupe_with_count());
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![("x", 0, 3), ("y", 3, 1)], result);
+    }
+
     #[test]
     fn test_channels() {
         let transducer = transducers::map(|x| x + 1);
@@ -266,4 +982,1573 @@ mod test {
         assert_eq!(2, rx.recv().unwrap());
         assert_eq!(3, rx.recv().unwrap());
     }
+
+    #[test]
+    fn test_channels_stop() {
+        let seen = Arc::new(AtomicUsize::new(0));
+        let producer_seen = seen.clone();
+        let transducer = super::compose(transducers::take(5), transducers::map(move |x: usize| {
+            producer_seen.fetch_add(1, Ordering::SeqCst);
+            x.to_string()
+        }));
+        let (mut tx, rx) = transducing_channel(transducer);
+        let producer = thread::spawn(move|| {
+            let mut sent = 0;
+            while tx.send(sent).unwrap() {
+                sent += 1;
+            }
+            assert!(!tx.send(sent + 1).unwrap());
+            tx.close().unwrap();
+            sent
+        });
+        assert_eq!(4, producer.join().unwrap());
+        assert_eq!(5, seen.load(Ordering::SeqCst));
+        let result: Vec<String> = rx.iter().collect();
+        assert_eq!(vec!["0", "1", "2", "3", "4"], result);
+    }
+
+    #[test]
+    fn test_running_extrema() {
+        let source = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::running_min()).unwrap();
+        assert_eq!(vec![3, 1, 1, 1, 1, 1, 1, 1], result);
+
+        let result = source.transduce_into::<(), _, _, _>(transducers::running_max()).unwrap();
+        assert_eq!(vec![3, 3, 4, 4, 5, 9, 9, 9], result);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let source = vec![1, 3, 5];
+        let result = source.transduce_into::<(), _, _, _>(transducers::merge_sorted(vec![2, 4, 6])).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], result);
+
+        let source = vec![4, 5];
+        let result = source.transduce_into::<(), _, _, _>(transducers::merge_sorted(vec![1, 2, 7, 8, 9])).unwrap();
+        assert_eq!(vec![1, 2, 4, 5, 7, 8, 9], result);
+
+        let source = vec![1, 3, 5];
+        let transducer = super::compose(transducers::take(3), transducers::merge_sorted(vec![2, 4, 6]));
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 2, 3], result);
+    }
+
+    #[test]
+    fn test_accumulate_until() {
+        let source = vec![1, 2, 3, 10, 4, 5, 10];
+        let transducer = transducers::accumulate_until(|_: &[usize], x: &usize| *x == 10);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![vec![1, 2, 3, 10], vec![4, 5, 10]], result);
+
+        let source = vec![1, 2, 3, 4, 5];
+        let transducer = transducers::accumulate_until(|buffer: &[usize], _: &usize| buffer.len() == 1);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], result);
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let source = vec![1, 2, 3];
+        let result = source.transduce_into::<(), _, _, _>(transducers::timestamp()).unwrap();
+        let values: Vec<usize> = result.iter().map(|&(_, x)| x).collect();
+        assert_eq!(vec![1, 2, 3], values);
+        assert!(result.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn test_latency_channels() {
+        let (mut tx, rx) = latency_transducing_channel(transducers::map(|x: usize| x * 2));
+        for i in 0..3 {
+            tx.send(i).unwrap();
+        }
+        tx.close().unwrap();
+        thread::sleep(Duration::from_millis(20));
+        for i in 0..3 {
+            let (latency, value) = rx.recv().unwrap();
+            assert_eq!(i * 2, value);
+            assert!(latency >= Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_zip_equal() {
+        let source = vec![1, 2, 3];
+        let transducer = transducers::zip_equal(vec!["a", "b", "c"]);
+        let result = source.transduce_try_into::<ZipLengthMismatch, _, _, _>(transducer);
+        assert_eq!(Ok(vec![(1, "a"), (2, "b"), (3, "c")]), result);
+
+        let source = vec![1, 2];
+        let transducer = transducers::zip_equal(vec!["a", "b", "c", "d"]);
+        let result = source.transduce_try_into::<ZipLengthMismatch, _, _, _>(transducer);
+        assert_eq!(Err(ZipLengthMismatch { expected: 4, got: 2 }), result);
+
+        let source = vec![1, 2, 3];
+        let transducer = transducers::zip_equal(vec!["a", "b"]);
+        let result = source.transduce_try_into::<ZipLengthMismatch, _, _, _>(transducer);
+        assert_eq!(Err(ZipLengthMismatch { expected: 2, got: 3 }), result);
+    }
+
+    #[test]
+    fn test_prepend_append() {
+        let result = vec![2, 3].transduce_into::<(), _, _, _>(transducers::prepend(vec![1])).unwrap();
+        assert_eq!(vec![1, 2, 3], result);
+
+        let result = vec![2, 3].transduce_into::<(), _, _, _>(transducers::append(vec![4])).unwrap();
+        assert_eq!(vec![2, 3, 4], result);
+
+        let transducer = super::compose(transducers::append(vec![4]), transducers::prepend(vec![1]));
+        let result = vec![2, 3].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], result);
+
+        let transducer = super::compose(transducers::append(vec![4]), transducers::prepend(vec![1]));
+        let result = Vec::<usize>::new().transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 4], result);
+    }
+
+    #[test]
+    fn test_flat_scan() {
+        let source = vec![1, 2, 0, 3, 0, 0, 4, 5, 6, 0];
+        let transducer = transducers::flat_scan(Vec::new(), |backlog: &mut Vec<usize>, x: usize| {
+            if x == 0 {
+                mem::take(backlog)
+            } else {
+                backlog.push(x);
+                Vec::new()
+            }
+        });
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], result);
+
+        let source = vec![1, 2, 3, 4];
+        let transducer = transducers::flat_scan(0, |total: &mut usize, x: usize| {
+            *total += x;
+            vec![*total; x % 3]
+        });
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 3, 3, 10], result);
+    }
+
+    #[test]
+    fn test_backfill() {
+        let source = vec![None, Some(1), None, None, Some(2), None];
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::backfill()).unwrap();
+        assert_eq!(vec![1, 1, 1, 2, 2], result);
+
+        let result = source.transduce_into::<(), _, _, _>(transducers::backfill_with_default(0)).unwrap();
+        assert_eq!(vec![0, 1, 1, 1, 2, 2], result);
+
+        let mut source = vec![None, Some(1), None, Some(2)];
+        source.reverse();
+        let mut result = source.transduce_into::<(), _, _, _>(transducers::backfill()).unwrap();
+        result.reverse();
+        assert_eq!(vec![1, 1, 2, 2], result);
+    }
+
+    #[test]
+    fn test_bounded_buffer() {
+        let source: Vec<usize> = (1..=10).collect();
+        let transducer = transducers::bounded_buffer(5, transducers::partition_all(3));
+        let result = source.clone().transduce_try_into::<BufferLimitExceeded, _, _, _>(transducer);
+        assert_eq!(Ok(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10]]), result);
+
+        let transducer = transducers::bounded_buffer(5, transducers::partition_all(8));
+        let result = source.transduce_try_into::<BufferLimitExceeded, _, _, _>(transducer);
+        assert_eq!(Err(BufferLimitExceeded { max: 5 }), result);
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        fn validators() -> Vec<Validator<(String, usize)>> {
+            vec![
+                Box::new(|(name, _)| if name.is_empty() {
+                    Err("name is empty".to_string())
+                } else {
+                    Ok(())
+                }),
+                Box::new(|&(_, age)| if age > 150 {
+                    Err(format!("age {} is out of range", age))
+                } else {
+                    Ok(())
+                })
+            ]
+        }
+        let valid = vec![("a".to_string(), 30), ("b".to_string(), 40)];
+        let result = valid.clone().transduce_try_into::<ValidationError, _, _, _>(transducers::validate_schema(validators()));
+        assert_eq!(Ok(valid), result);
+
+        let invalid = vec![("a".to_string(), 30), ("".to_string(), 200)];
+        let result = invalid.clone().transduce_try_into::<ValidationError, _, _, _>(transducers::validate_schema(validators()));
+        assert_eq!(Err(ValidationError { failures: vec!["name is empty".to_string()] }), result);
+
+        let transducer = transducers::validate_schema_collect_all_errors(validators());
+        let result = invalid.transduce_try_into::<ValidationError, _, _, _>(transducer);
+        let failures = vec!["name is empty".to_string(), "age 200 is out of range".to_string()];
+        assert_eq!(Err(ValidationError { failures }), result);
+    }
+
+    #[test]
+    fn test_map_err() {
+        #[derive(Debug, PartialEq)]
+        enum PipelineError {
+            Parse(ParseIntError),
+            Rejected(String)
+        }
+
+        impl From<ParseIntError> for PipelineError {
+            fn from(e: ParseIntError) -> Self {
+                PipelineError::Parse(e)
+            }
+        }
+
+        fn run(source: Vec<&str>) -> Result<Vec<usize>, PipelineError> {
+            let mut reducer = fn_reducer(Vec::new(), |acc: &mut Vec<usize>, x: usize| if x > 100 {
+                Err(format!("{} is too large", x))
+            } else {
+                acc.push(x);
+                Ok(StepResult::Continue)
+            });
+            let transducer = super::compose(transducers::map_err(PipelineError::Rejected),
+                                            transducers::try_map(|s: &str| s.parse::<usize>()));
+            drive(source, transducer, &mut reducer)?;
+            Ok(reducer.finish())
+        }
+
+        assert_eq!(Ok(vec![1, 2]), run(vec!["1", "2"]));
+        assert_eq!(Err(PipelineError::Rejected("500 is too large".to_string())), run(vec!["1", "500"]));
+        match run(vec!["1", "x"]) {
+            Err(PipelineError::Parse(_)) => (),
+            other => panic!("expected a parse error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn test_recover_with() {
+        let source = vec!["1", "x", "3"];
+        let transducer = super::compose(transducers::try_map(|s: &str| s.parse::<usize>()),
+                                        transducers::recover_with(|_: &ParseIntError| Some("0")));
+        let result = source.clone().transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(Ok(vec![1, 0, 3]), result);
+
+        let transducer = super::compose(transducers::try_map(|s: &str| s.parse::<usize>()),
+                                        transducers::recover_with(|_: &ParseIntError| None));
+        let result = source.clone().transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert!(result.is_err());
+
+        let transducer = super::compose(transducers::try_map(|s: &str| s.parse::<usize>()),
+                                        transducers::recover_with(|_: &ParseIntError| Some("y")));
+        let result = source.transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_count_errors() {
+        let source = vec!["1", "x", "3", "y", "z"];
+        let (counter, errors) = transducers::count_errors();
+        let transducer = super::compose(transducers::try_map(|s: &str| s.parse::<usize>()), counter);
+        let result = source.transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(Ok(vec![1, 3]), result);
+        assert_eq!(3, errors.get());
+    }
+
+    #[test]
+    fn test_channels_into_vec() {
+        let (mut tx, rx) = transducing_channel(transducers::partition_all(3));
+        thread::spawn(move|| {
+            for i in 0..7 {
+                tx.send(i).unwrap();
+            }
+            tx.close().unwrap();
+        });
+        let expected_result: Vec<Vec<usize>> = vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]];
+        assert_eq!(expected_result, rx.into_vec());
+    }
+
+    #[test]
+    fn test_limit_errors() {
+        let parse = |s: &str| s.parse::<usize>();
+        let source = vec!["1", "x", "3", "y", "5"];
+
+        let transducer = super::compose(transducers::try_map(parse), transducers::limit_errors(2));
+        let result = source.clone().transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(Ok(vec![1, 3, 5]), result);
+
+        let transducer = super::compose(transducers::try_map(parse), transducers::limit_errors(1));
+        let result = source.clone().transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert!(result.is_err());
+
+        let transducer = super::compose(transducers::try_map(parse), transducers::limit_errors(0));
+        let result = source.clone().transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert!(result.is_err());
+
+        let (limit, errors) = transducers::limit_errors_collect(2);
+        let transducer = super::compose(transducers::try_map(parse), limit);
+        let result = source.transduce_try_into::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(Ok(vec![1, 3, 5]), result);
+        assert_eq!(2, errors.take().len());
+
+        let (limit, errors) = transducers::limit_errors_collect(2);
+        let transducer = super::compose(transducers::try_map(parse), limit);
+        let result = thread::spawn(move || {
+            vec!["x", "2", "y"].transduce_try_into::<ParseIntError, _, _, _>(transducer)
+        }).join().unwrap();
+        assert_eq!(Ok(vec![2]), result);
+        assert_eq!(2, errors.take().len());
+    }
+
+    #[test]
+    fn test_retry_map() {
+        let failures = Cell::new(0);
+        let flaky = |x: &usize| if failures.get() < 2 {
+            failures.set(failures.get() + 1);
+            Err(format!("failed on {}", x))
+        } else {
+            failures.set(0);
+            Ok(x * 10)
+        };
+        let source = vec![1, 2, 3];
+        let result = source.clone().transduce_try_into::<String, _, _, _>(transducers::retry_map(3, &flaky));
+        assert_eq!(Ok(vec![10, 20, 30]), result);
+
+        let result = source.transduce_try_into::<String, _, _, _>(transducers::retry_map(2, &flaky));
+        assert_eq!(Err("failed on 1".to_string()), result);
+    }
+
+    #[test]
+    fn test_flatten_results() {
+        let source: Vec<Result<usize, &str>> = vec![Ok(1), Ok(2), Err("bad"), Ok(3)];
+        let (result, err) = source.clone().transduce_collect_partial::<&str, _, _, _>(transducers::flatten_results());
+        assert_eq!(vec![1, 2], result);
+        assert_eq!(Some("bad"), err);
+
+        let result = source.transduce_into::<(), _, _, _>(transducers::flatten_results_skip());
+        assert_eq!(Ok(vec![1, 2, 3]), result);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let source = vec!["Alice".to_string(), "bob".to_string()];
+        let pointers: Vec<*const u8> = source.iter().map(|s| s.as_ptr()).collect();
+        let transducer = transducers::map_in_place(|s: &mut String| s.make_ascii_lowercase());
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec!["alice", "bob"], result);
+        let result_pointers: Vec<*const u8> = result.iter().map(|s| s.as_ptr()).collect();
+        assert_eq!(pointers, result_pointers);
+    }
+
+    #[test]
+    fn test_stateful_transducer() {
+        let partition_two = || {
+            transducers::stateful_transducer(
+                Vec::new,
+                |holder: &mut Vec<usize>, x: usize, push: &mut dyn FnMut(Vec<usize>)| {
+                    holder.push(x);
+                    if holder.len() == 2 {
+                        push(mem::take(holder));
+                    }
+                    Ok::<_, ()>(StepResult::Continue)
+                },
+                |_, _| Ok(()))
+        };
+
+        let source = vec![1, 2, 3, 4, 5];
+        let expected: Vec<Vec<usize>> = source.clone().transduce_into::<(), _, _, _>(transducers::partition(2)).unwrap();
+        let result: Vec<Vec<usize>> = source.transduce_into(partition_two()).unwrap();
+        assert_eq!(expected, result);
+
+        let expected: Vec<Vec<usize>> = vec![1, 2, 3, 4, 5, 6].transduce_into::<(), _, _, _>(super::compose(transducers::take(2), transducers::partition(2))).unwrap();
+        let result: Vec<Vec<usize>> = vec![1, 2, 3, 4, 5, 6].transduce_into(super::compose(transducers::take(2), partition_two())).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_contextual_map() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let transducer = super::compose(
+            transducers::contextual_map(counter.clone(), |c: &AtomicUsize, x: usize| {
+                c.fetch_add(1, Ordering::SeqCst);
+                x * 10
+            }),
+            transducers::contextual_map(counter.clone(), |c: &AtomicUsize, x: usize| {
+                c.fetch_add(1, Ordering::SeqCst);
+                x + 1
+            }));
+        let result = vec![1, 2, 3].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![20, 30, 40], result);
+        assert_eq!(6, counter.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let counter = |s: usize| if s <= 3 { Some((s * 10, s + 1)) } else { None };
+
+        let result = vec![(), ()].transduce_into::<(), _, _, _>(transducers::unfold(1, counter)).unwrap();
+        assert_eq!(vec![10, 20, 30], result);
+
+        let result = Vec::<()>::new().transduce_into::<(), _, _, _>(transducers::unfold(1, counter)).unwrap();
+        assert!(result.is_empty());
+
+        let transducer = super::compose(transducers::take(2), transducers::unfold(1, counter));
+        let result = vec!["a", "b"].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![10, 20], result);
+
+        let naturals = |s: usize| Some((s, s + 1));
+        let transducer = super::compose(transducers::take(4), transducers::unfold(0, naturals));
+        let result = vec![()].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![0, 1, 2, 3], result);
+    }
+
+    #[test]
+    fn test_generate_n() {
+        let result = vec![10, 20].transduce_into::<(), _, _, _>(transducers::generate_n(3, |i| i as u64)).unwrap();
+        assert_eq!(vec![0, 1, 2, 10, 20], result);
+
+        let result = Vec::<u64>::new().transduce_into::<(), _, _, _>(transducers::generate_n(2, |i| i as u64 * 5)).unwrap();
+        assert_eq!(vec![0, 5], result);
+
+        let transducer = super::compose(transducers::take(2), transducers::generate_n(3, |i| i as u64));
+        let result = vec![10, 20].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![0, 1], result);
+    }
+
+    #[test]
+    fn test_rechunk() {
+        let source = vec![vec![1, 2, 3], vec![4], vec![5, 6]];
+        let result = source.transduce_into::<(), _, _, _>(transducers::rechunk(2)).unwrap();
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5, 6]], result);
+
+        let source = vec![vec![1], vec![], vec![2, 3, 4, 5, 6, 7], vec![8]];
+        let result = source.transduce_into::<(), _, _, _>(transducers::rechunk(3)).unwrap();
+        assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]], result);
+
+        let transducer = super::compose(transducers::take(1), transducers::rechunk(2));
+        let result = vec![vec![1], vec![2, 3, 4]].transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![vec![1, 2]], result);
+    }
+
+    #[test]
+    fn test_interleave_n() {
+        let result = (1..7).collect::<Vec<usize>>().transduce_into::<(), _, _, _>(transducers::interleave_n(2)).unwrap();
+        assert_eq!(vec![(0, 1), (1, 2), (0, 3), (1, 4), (0, 5), (1, 6)], result);
+
+        for k in 0..3 {
+            let transducer = super::compose(transducers::map(|(_, v): (usize, usize)| v),
+                                            super::compose(transducers::filter(move |&(idx, _): &(usize, usize)| idx == k),
+                                                           transducers::interleave_n(3)));
+            let result = (0..9).collect::<Vec<usize>>().transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![k, k + 3, k + 6], result);
+        }
+    }
+
+    #[test]
+    fn test_concurrent_map() {
+        let slow_square = |x: u64| {
+            thread::sleep(Duration::from_millis(5 - x % 5));
+            x * x
+        };
+        let source: Vec<u64> = (0..40).collect();
+        let expected = source.clone().transduce_into::<(), _, _, _>(transducers::map(slow_square)).unwrap();
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::concurrent_map(4, slow_square)).unwrap();
+        assert_eq!(expected, result);
+
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::concurrent_map_default(slow_square)).unwrap();
+        assert_eq!(expected, result);
+
+        let transducer = super::compose(transducers::take(5), transducers::concurrent_map(3, slow_square));
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![0, 1, 4, 9, 16], result);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad value")]
+    fn test_concurrent_map_panic() {
+        let transducer = transducers::concurrent_map(2, |x: u64| if x == 3 { panic!("bad value") } else { x });
+        let _ = (0..10).collect::<Vec<u64>>().transduce_into::<(), _, _, _>(transducer);
+    }
+
+    #[test]
+    fn test_stateful() {
+        let tokenize = |word: &mut String, c: char| {
+            if c.is_whitespace() {
+                if word.is_empty() { None } else { Some(mem::take(word)) }
+            } else {
+                word.push(c);
+                None
+            }
+        };
+        let source: Vec<char> = "let  x = 10".chars().collect();
+
+        let result = source.clone().transduce_into::<(), _, _, _>(transducers::stateful(String::new(), tokenize)).unwrap();
+        assert_eq!(vec!["let", "x", "="], result);
+
+        let transducer = transducers::stateful_with_complete(String::new(), tokenize,
+                                                             |word| if word.is_empty() { None } else { Some(word) });
+        let result = source.clone().transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec!["let", "x", "=", "10"], result);
+
+        let transducer = super::compose(transducers::take(1),
+                                        transducers::stateful_with_complete(String::new(), tokenize, Some));
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec!["let"], result);
+    }
+
+    #[test]
+    fn test_peak_detector() {
+        {
+            let source = vec![1, 3, 2, 2, 5, 4, 6];
+            let transducer = transducers::peak_detector();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![3, 5], result);
+        }
+        {
+            let source = vec![5, 1, 3, 0, 0, 2, 1];
+            let transducer = transducers::valley_detector();
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![1], result);
+        }
+    }
+
+    #[test]
+    fn test_into_sorted() {
+        {
+            let source = vec![5, 3, 8, 1, 9, 2];
+            let transducer = transducers::map(|x| x * 10);
+            let result = source.transduce_into_sorted::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![10, 20, 30, 50, 80, 90], result);
+        }
+        {
+            let source = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+            let transducer = transducers::map(|x| x);
+            let result = source.transduce_into_sorted_by::<(), _, _, _, _>(transducer, |a: &(usize, char), b| b.0.cmp(&a.0))
+                .unwrap();
+            assert_eq!(vec![(2, 'a'), (2, 'c'), (1, 'b'), (1, 'd')], result);
+        }
+    }
+
+    #[test]
+    fn test_bounded_channels() {
+        let transducer = transducers::map(|x: usize| x.to_string());
+        let (mut tx, rx) = bounded_transducing_channel(2, transducer);
+        let (sent_tx, sent_rx) = mpsc::channel();
+        let producer = thread::spawn(move|| {
+            for i in 0..3 {
+                tx.send(i).unwrap();
+                sent_tx.send(i).unwrap();
+            }
+            tx.close().unwrap();
+        });
+        assert_eq!(0, sent_rx.recv().unwrap());
+        assert_eq!(1, sent_rx.recv().unwrap());
+        assert_eq!(Err(mpsc::TryRecvError::Empty), sent_rx.try_recv());
+        assert_eq!("0", rx.recv().unwrap());
+        assert_eq!(2, sent_rx.recv().unwrap());
+        producer.join().unwrap();
+        assert_eq!("1", rx.recv().unwrap());
+        assert_eq!("2", rx.recv().unwrap());
+    }
+
+    /// Fails as if downstream were full, ignoring the channel it is given
+    struct FullTransducer;
+
+    struct FullReducer;
+
+    impl<RI> Transducer<RI> for FullTransducer {
+        type RO = FullReducer;
+
+        fn new(self, _: RI) -> FullReducer {
+            FullReducer
+        }
+    }
+
+    impl Reducing<usize, (), TrySendError<usize>> for FullReducer {
+        type Item = usize;
+
+        fn step(&mut self, value: usize) -> Result<StepResult, TrySendError<usize>> {
+            Err(TrySendError::Full(value))
+        }
+
+        fn complete(&mut self) -> Result<(), TrySendError<usize>> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_bounded_channels_full_downstream() {
+        let (mut tx, _rx) = bounded_transducing_channel::<usize, usize, _, _>(1, FullTransducer);
+        assert_eq!(Err(mpsc::SendError(1)), tx.send(1));
+    }
+
+    #[test]
+    fn test_bounded_channels_try_send() {
+        let transducer = transducers::map(|x| x + 1);
+        let (mut tx, rx) = bounded_transducing_channel(1, transducer);
+        assert!(tx.try_send(1).unwrap());
+        match tx.try_send(2) {
+            Err(TrySendError::Full(3)) => (),
+            _ => panic!("expected a full channel")
+        }
+        assert_eq!(2, rx.recv().unwrap());
+        assert!(tx.try_send(3).unwrap());
+        assert_eq!(4, rx.recv().unwrap());
+    }
+
+    #[test]
+    fn test_multi_producer_channels() {
+        let transducer = transducers::map(|x: usize| x * 2);
+        let (factory, rx) = multi_producer_transducing_channel(transducer);
+        for p in 0..3 {
+            let mut tx = factory.new_sender();
+            thread::spawn(move|| {
+                for i in 0..100 {
+                    tx.send(p * 100 + i).unwrap();
+                }
+                tx.close().unwrap();
+            });
+        }
+        drop(factory);
+        let mut result:Vec<usize> = rx.iter().collect();
+        result.sort();
+        let expected_result:Vec<usize> = (0..300).map(|x| x * 2).collect();
+        assert_eq!(expected_result, result);
+    }
+
+    #[test]
+    fn test_multi_producer_channels_complete_once() {
+        let (factory, rx) = multi_producer_transducing_channel(transducers::partition_all(2));
+        let mut tx_a = factory.new_sender();
+        let mut tx_b = factory.new_sender();
+        tx_a.send(1).unwrap();
+        tx_a.close().unwrap();
+        tx_a.close().unwrap();
+        tx_b.send(2).unwrap();
+        tx_b.send(3).unwrap();
+        tx_b.close().unwrap();
+
+        let mut tx_c = factory.new_sender();
+        assert!(!tx_c.send(4).unwrap());
+        tx_c.close().unwrap();
+        drop((factory, tx_a, tx_b, tx_c));
+        let result:Vec<Vec<usize>> = rx.iter().collect();
+        assert_eq!(vec![vec![1, 2], vec![3]], result);
+    }
+
+    #[test]
+    fn test_write_lines() {
+        {
+            let source = vec![1, 2, 3];
+            let transducer = transducers::map(|x| x * 2);
+            let mut result = Vec::new();
+            source.transduce_write_lines(&mut result, transducer).unwrap();
+            assert_eq!(b"2\n4\n6\n", &result[..]);
+        }
+        {
+            let source = vec![1, 2, 3];
+            let transducer = transducers::map(|x| x * 2);
+            let mut buffer = [0u8; 4];
+            let result = source.transduce_write_lines(&mut buffer[..], transducer);
+            assert_eq!(io::ErrorKind::WriteZero, result.unwrap_err().kind());
+            assert_eq!(b"2\n4\n", &buffer);
+        }
+    }
+
+    #[test]
+    fn test_select_channels() {
+        let (mut tx_a, rx_a) = transducing_channel(transducers::map(|x: usize| x * 2));
+        let (mut tx_b, rx_b) = transducing_channel(transducers::filter(|x| x % 2 == 1));
+        thread::spawn(move|| {
+            for i in 0..10 {
+                tx_a.send(i).unwrap();
+                thread::sleep(Duration::from_millis(2));
+            }
+            tx_a.close().unwrap();
+        });
+        thread::spawn(move|| {
+            for i in 100..200 {
+                tx_b.send(i).unwrap();
+            }
+            tx_b.close().unwrap();
+        });
+        let mut result:Vec<usize> = select_transducing_channels(vec![rx_a, rx_b]).collect();
+        result.sort();
+        let mut expected_result:Vec<usize> = (0..10).map(|x| x * 2).collect();
+        expected_result.extend((100..200).filter(|x| x % 2 == 1));
+        assert_eq!(expected_result, result);
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn test_crossbeam_select_channels() {
+        use super::applications::crossbeam_channels;
+
+        let (mut tx_a, rx_a) = crossbeam_channels::transducing_channel(transducers::map(|x: usize| x * 2));
+        let (mut tx_b, rx_b) = crossbeam_channels::transducing_channel(transducers::filter(|x| x % 2 == 1));
+        thread::spawn(move|| {
+            for i in 0..10 {
+                tx_a.send(i).unwrap();
+                thread::sleep(Duration::from_millis(2));
+            }
+            tx_a.close().unwrap();
+        });
+        thread::spawn(move|| {
+            for i in 100..200 {
+                tx_b.send(i).unwrap();
+            }
+            tx_b.close().unwrap();
+        });
+        let mut result:Vec<usize> = crossbeam_channels::select_transducing_channels(vec![rx_a, rx_b]).collect();
+        result.sort();
+        let mut expected_result:Vec<usize> = (0..10).map(|x| x * 2).collect();
+        expected_result.extend((100..200).filter(|x| x % 2 == 1));
+        assert_eq!(expected_result, result);
+    }
+
+    #[test]
+    fn test_transduce_to_sender() {
+        let (tx, rx) = mpsc::channel();
+        let consumer = thread::spawn(move|| rx.into_iter().collect::<Vec<Vec<usize>>>());
+        let transducer = super::compose(transducers::partition_all(2), transducers::map(|x: usize| x * 10));
+        vec![1, 2, 3, 4, 5].transduce_to_sender(tx, transducer).unwrap();
+        assert_eq!(vec![vec![10, 20], vec![30, 40], vec![50]], consumer.join().unwrap());
+
+        let (tx, rx) = mpsc::channel();
+        vec![1, 2, 3, 4, 5].transduce_to_sender(tx, transducers::take(2)).unwrap();
+        assert_eq!(vec![1, 2], rx.into_vec());
+    }
+
+    #[test]
+    fn test_transducing_select() {
+        let (tx_a, rx_a) = mpsc::channel();
+        let (tx_b, rx_b) = mpsc::channel();
+        thread::spawn(move|| {
+            for i in 0..10 {
+                tx_a.send(i).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        thread::spawn(move|| {
+            for i in 100..150 {
+                tx_b.send(i).unwrap();
+            }
+        });
+        let transducer = super::compose(transducers::partition_all(7), transducers::map(|x: usize| x + 1));
+        let chunks: Vec<Vec<usize>> = transducing_select(vec![rx_a, rx_b], transducer).collect();
+        assert_eq!(9, chunks.len());
+        assert_eq!(4, chunks[8].len());
+        let mut result: Vec<usize> = chunks.into_iter().flatten().collect();
+        result.sort();
+        let mut expected_result: Vec<usize> = (1..11).collect();
+        expected_result.extend(101..151);
+        assert_eq!(expected_result, result);
+    }
+
+    #[test]
+    fn test_transduce_ref_drain_errors() {
+        let source = vec!["1", "2", "3"];
+        let parse = |s: &&str| s.parse::<usize>();
+        let result = source.transduce_ref::<ParseIntError, _, _, _>(transducers::try_map(parse));
+        assert_eq!(Ok(vec![1, 2, 3]), result);
+
+        let source = vec!["1", "x", "3"];
+        assert!(source.transduce_ref::<ParseIntError, _, _, _>(transducers::try_map(parse)).is_err());
+        assert_eq!(3, source.len());
+
+        let mut source = vec!["4", "5"];
+        let result = source.transduce_drain::<ParseIntError, _, _, _>(transducers::try_map(|s: &str| s.parse::<usize>()));
+        assert_eq!(Ok(vec![4, 5]), result);
+        assert!(source.is_empty());
+    }
+
+    #[test]
+    fn test_transduce_sorted_grouped_errors() {
+        let parse = |s: &str| s.parse::<usize>();
+        let source = vec!["3", "1", "2"];
+        let result = source.clone().transduce_into_sorted_vec::<ParseIntError, _, _, _>(transducers::try_map(parse));
+        assert_eq!(Ok(vec![1, 2, 3]), result);
+        let result = source.transduce_count_by_ordered::<ParseIntError, _, _, _, _, _>(|x: &usize| x % 2, transducers::try_map(parse));
+        assert_eq!(Ok(vec![(1, 2), (0, 1)]), result);
+
+        let source = vec!["3", "x", "2"];
+        assert!(source.clone().transduce_into_sorted_vec::<ParseIntError, _, _, _>(transducers::try_map(parse)).is_err());
+        assert!(source.transduce_group_into_vec::<ParseIntError, _, _, _, _, _>(|x: &usize| *x, transducers::try_map(parse)).is_err());
+    }
+
+    #[test]
+    fn test_transduce_collect_partial() {
+        let source = vec!["1", "2", "x", "4"];
+        let transducer = transducers::try_map(|s: &str| s.parse::<usize>());
+        let (result, err) = source.transduce_collect_partial::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(vec![1, 2], result);
+        assert!(err.is_some());
+
+        let source = vec!["1", "2"];
+        let transducer = transducers::try_map(|s: &str| s.parse::<usize>());
+        let (result, err) = source.transduce_collect_partial::<ParseIntError, _, _, _>(transducer);
+        assert_eq!(vec![1, 2], result);
+        assert!(err.is_none());
+    }
+
+    #[test]
+    fn test_transduce_unzip() {
+        let source = vec![(1, "a"), (2, "b"), (3, "c")];
+        let result = source.clone().transduce_unzip(transducers::identity()).unwrap();
+        assert_eq!((vec![1, 2, 3], vec!["a", "b", "c"]), result);
+
+        let transducer = transducers::filter(|&(x, _): &(usize, &str)| x != 2);
+        let result = source.transduce_unzip_with(transducer, |x: usize| x * 10, |s: &str| s.to_uppercase()).unwrap();
+        assert_eq!((vec![10, 30], vec!["A".to_string(), "C".to_string()]), result);
+    }
+
+    #[test]
+    fn test_transduce_partition_results() {
+        let source: Vec<Result<usize, &str>> = vec![Ok(1), Err("a"), Ok(2), Err("b")];
+        let result = source.transduce_partition_results(transducers::identity()).unwrap();
+        assert_eq!((vec![1, 2], vec!["a", "b"]), result);
+
+        let source = vec!["1", "x", "3"];
+        let transducer = transducers::map(|s: &str| s.parse::<usize>());
+        let (parsed, errors) = source.transduce_partition_results(transducer).unwrap();
+        assert_eq!(vec![1, 3], parsed);
+        assert_eq!(1, errors.len());
+    }
+
+    #[test]
+    fn test_transduce_fold_while() {
+        let sum_until = |acc: usize, x: usize| {
+            let sum = acc + x;
+            (sum, if sum > 6 { StepResult::Stop } else { StepResult::Continue })
+        };
+        let result = vec![1, 2, 3, 4, 5].transduce_fold_while(0, sum_until, transducers::identity()).unwrap();
+        assert_eq!(10, result);
+
+        let result = vec![1, 2].transduce_fold_while(0, sum_until, transducers::identity()).unwrap();
+        assert_eq!(3, result);
+
+        let count_groups = |acc: usize, _: Vec<usize>| (acc + 1, if acc + 1 == 2 { StepResult::Stop } else { StepResult::Continue });
+        let result = vec![1, 1, 2, 3].transduce_fold_while(0, count_groups, transducers::partition_by(|x: &usize| *x)).unwrap();
+        assert_eq!(2, result);
+    }
+
+    #[test]
+    fn test_transduce_into_with() {
+        let mut ctx: Vec<usize> = vec![0];
+        vec![1, 2, 3, 4].transduce_into_with(&mut ctx, |ctx: &mut Vec<usize>, x| ctx.push(x),
+                                             transducers::map(|x: usize| x * 10)).unwrap();
+        vec![5, 6].transduce_into_with(&mut ctx, |ctx: &mut Vec<usize>, x| ctx.push(x),
+                                       transducers::identity()).unwrap();
+        assert_eq!(vec![0, 10, 20, 30, 40, 5, 6], ctx);
+    }
+
+    #[test]
+    fn test_transduce_stateless_into() {
+        let source: Vec<usize> = (0..100).collect();
+        let transducer = || super::compose(transducers::map(|x: usize| x.to_string()),
+                                           transducers::map(|x: usize| x * 3));
+        let expected = source.clone().transduce_into::<(), _, _, _>(transducer()).unwrap();
+        let result = source.transduce_stateless_into(transducer());
+        assert_eq!(expected, result);
+
+        let result = vec![1, 2].transduce_stateless_into(transducers::identity());
+        assert_eq!(vec![1, 2], result);
+    }
+
+    #[test]
+    fn test_transduce_into_or_move() {
+        let result = vec![1, 2, 3].transduce_into_or_move::<(), _, _>(transducers::map(|x: usize| x * 2)).unwrap();
+        assert_eq!(vec![2, 4, 6], result);
+
+        let source = vec![1, 2, 3];
+        let ptr = source.as_ptr();
+        let result = source.transduce_into_or_move::<(), _, _>(transducers::identity()).unwrap();
+        assert_eq!(vec![1, 2, 3], result);
+        assert_eq!(ptr, result.as_ptr());
+    }
+
+    #[test]
+    fn test_transduce_count_by_ordered() {
+        let words = vec!["pear", "apple", "plum", "banana", "apricot", "peach", "blueberry"];
+        let result = words.transduce_count_by_ordered::<(), _, _, _, _, _>(|w: &&str| w.chars().next().unwrap(),
+                                                      transducers::identity()).unwrap();
+        assert_eq!(vec![('p', 3), ('a', 2), ('b', 2)], result);
+
+        let words = vec!["kiwi", "fig", "date", "lime"];
+        let result = words.transduce_count_by_ordered::<(), _, _, _, _, _>(|len: &usize| *len,
+                                                      transducers::map(|w: &str| w.len())).unwrap();
+        assert_eq!(vec![(4, 3), (3, 1)], result);
+    }
+
+    #[test]
+    fn test_transduce_group_into_vec() {
+        let source = vec![(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e'), (1, 'f')];
+        let result = source.transduce_group_into_vec::<(), _, _, _, _, _>(|&(k, _): &(usize, char)| k,
+                                                     transducers::filter(|&(_, c): &(usize, char)| c != 'd')).unwrap();
+        assert_eq!(vec![(1, vec![(1, 'a'), (1, 'c'), (1, 'f')]),
+                        (2, vec![(2, 'b'), (2, 'e')])], result);
+
+        let words = vec!["kiwi", "fig", "date", "yam", "lime"];
+        let result = words.transduce_group_into_vec::<(), _, _, _, _, _>(|w: &String| w.len(),
+                                                    transducers::map(|w: &str| w.to_uppercase())).unwrap();
+        assert_eq!(vec![(4, vec![String::from("KIWI"), String::from("DATE"), String::from("LIME")]),
+                        (3, vec![String::from("FIG"), String::from("YAM")])], result);
+    }
+
+    #[test]
+    fn test_transduce_into_instrumented() {
+        let source: Vec<usize> = (1..8).collect();
+        let (result, stages) = source.clone().transduce_into_instrumented::<(), _, _, _, _>(|stats| {
+            super::compose(stats.stage("square", transducers::map(|x: usize| x * x)),
+                           stats.stage("odd", transducers::filter(|x: &usize| x % 2 == 1)))
+        }).unwrap();
+        assert_eq!(vec![1, 9, 25, 49], result);
+        assert_eq!(2, stages.len());
+        assert_eq!(("odd", 7, 4, 3), (stages[0].name, stages[0].received, stages[0].passed, stages[0].dropped()));
+        assert_eq!(("square", 4, 4, 0), (stages[1].name, stages[1].received, stages[1].passed, stages[1].dropped()));
+
+        let (_, stages) = source.transduce_into_instrumented::<(), _, _, _, _>(|stats| {
+            let inner = super::compose(stats.stage("take", transducers::take(2)),
+                                       stats.stage("odd", transducers::filter(|x: &usize| x % 2 == 1)));
+            super::compose(stats.stage("double", transducers::map(|x: usize| x * 2)),
+                           stats.stage("all", inner))
+        }).unwrap();
+        let names: Vec<&str> = stages.iter().map(|s| s.name).collect();
+        assert_eq!(vec!["all", "odd", "take", "double"], names);
+        assert_eq!(("all", 3, 2), (stages[0].name, stages[0].received, stages[0].passed));
+        assert_eq!(("odd", 3, 2), (stages[1].name, stages[1].received, stages[1].passed));
+        assert_eq!(("take", 2, 2), (stages[2].name, stages[2].received, stages[2].passed));
+        assert_eq!(("double", 2, 2), (stages[3].name, stages[3].received, stages[3].passed));
+    }
+
+    #[test]
+    fn test_transduce_into_with_stop() {
+        let source = vec![1, 2, 3, 4, 5];
+        assert_eq!(Ok((vec![1, 2, 3], true)), source.clone().transduce_into_with_stop::<(), _, _, _>(transducers::take(3)));
+        assert_eq!(Ok((vec![1, 2, 3, 4, 5], false)), source.clone().transduce_into_with_stop::<(), _, _, _>(transducers::take(5)));
+        assert_eq!(Ok((vec![1, 2, 3, 4, 5], false)), source.clone().transduce_into_with_stop::<(), _, _, _>(transducers::take(10)));
+
+        let transducer = super::compose(transducers::partition_all(2), transducers::take(3));
+        assert_eq!(Ok((vec![vec![1, 2], vec![3]], true)), source.transduce_into_with_stop::<(), _, _, _>(transducer));
+    }
+
+    #[test]
+    fn test_transduce_append() {
+        let mut target = vec![0];
+        vec![1, 2, 3].transduce_append::<(), _, _, _>(&mut target, transducers::map(|x: usize| x * 10)).unwrap();
+        vec![4, 5, 6].transduce_append::<(), _, _, _>(&mut target, transducers::filter(|x: &usize| *x > 4)).unwrap();
+        assert_eq!(vec![0, 10, 20, 30, 5, 6], target);
+    }
+
+    #[test]
+    fn test_transduce_fold() {
+        let result = super::transduce_fold(1..=5, transducers::filter(|x: &usize| *x == 2 || *x == 4), 0, |acc, x| acc + x);
+        assert_eq!(Ok(6), result);
+
+        let result = super::transduce_fold(vec!["a", "b", "c"], transducers::take(2), String::new(), |mut acc, x| {
+            acc.push_str(x);
+            acc
+        });
+        assert_eq!(Ok(String::from("ab")), result);
+    }
+
+    #[test]
+    fn test_transduce_any_all() {
+        let seen = Cell::new(0);
+        let tap = transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x
+        });
+        assert_eq!(Ok(true), super::transduce_any(1..=1_000_000, tap, |x: &usize| *x == 42));
+        assert_eq!(42, seen.get());
+        assert_eq!(Ok(false), super::transduce_any(1..=10, transducers::map(|x: usize| x * 2), |x: &usize| *x == 5));
+
+        seen.set(0);
+        let tap = transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x
+        });
+        assert_eq!(Ok(false), super::transduce_all(1..=1_000_000, tap, |x: &usize| *x < 10));
+        assert_eq!(10, seen.get());
+        assert_eq!(Ok(true), super::transduce_all(1..=10, transducers::map(|x: usize| x * 2), |x: &usize| *x > 1));
+        assert_eq!(Ok(true), super::transduce_all(Vec::<usize>::new(), transducers::map(|x: usize| x), |_: &usize| false));
+    }
+
+    #[test]
+    fn test_transduce_sum_product() {
+        assert_eq!(Ok(2550), super::transduce_sum(1..=100, transducers::filter(|x: &u32| x % 2 != 1)));
+        assert_eq!(Ok(0), super::transduce_sum(1..=10, transducers::filter(|x: &u32| *x > 10)));
+        assert_eq!(Ok(120), super::transduce_product(1..=10, transducers::take(5)));
+        assert_eq!(Ok(1.0), super::transduce_product(Vec::<f64>::new(), transducers::map(|x: f64| x)));
+
+        let words = vec!["fig", "kiwi", "date", "banana", "yam"];
+        assert_eq!(Ok(Some(3)), super::transduce_min(words.clone(), transducers::map(|w: &str| w.len())));
+        assert_eq!(Ok(Some(6)), super::transduce_max(words, transducers::map(|w: &str| w.len())));
+        assert_eq!(Ok(None), super::transduce_max(1..=10, transducers::filter(|x: &u32| *x > 10)));
+    }
+
+    #[test]
+    fn test_transduce_count() {
+        assert_eq!(Ok(5), super::transduce_count(1..=10, transducers::filter(|x: &usize| *x > 5)));
+        assert_eq!(Ok(6), super::transduce_count(1..=3, transducers::mapcat(|x: usize| vec![x, x])));
+        assert_eq!(Ok(4), super::transduce_count(1..=10, transducers::partition_all(3)));
+    }
+
+    #[test]
+    fn test_transduce_first_last() {
+        let seen = Cell::new(0);
+        let transducer = super::compose(transducers::filter(|x: &usize| *x > 5),
+                                        transducers::map(|x: usize| {
+                                            seen.set(x);
+                                            x
+                                        }));
+        assert_eq!(Ok(Some(6)), super::transduce_first(1..=10, transducer));
+        assert_eq!(6, seen.get());
+
+        assert_eq!(Ok(None), super::transduce_first(1..=5, transducers::filter(|x: &usize| *x > 5)));
+
+        let result = super::transduce_first(vec![1, 1, 2, 3], transducers::partition_by(|x: &usize| *x));
+        assert_eq!(Ok(Some(vec![1, 1])), result);
+
+        let result = super::transduce_last(1..=10, transducers::partition_all(3));
+        assert_eq!(Ok(Some(vec![10])), result);
+        assert_eq!(Ok(None), super::transduce_last(Vec::<usize>::new(), transducers::map(|x: usize| x)));
+    }
+
+    #[test]
+    fn test_transduce_cycle() {
+        let result = vec![1, 2, 3].transduce_cycle::<(), _, _, _, _>(3, || transducers::map(|x: usize| x * 2)).unwrap();
+        assert_eq!(vec![2, 4, 6, 2, 4, 6, 2, 4, 6], result);
+
+        let result = vec![1, 2, 3].transduce_cycle::<(), _, _, _, _>(2, || transducers::take(2)).unwrap();
+        assert_eq!(vec![1, 2, 1, 2], result);
+
+        let result = vec![1, 2, 3].transduce_cycle::<(), _, _, _, _>(0, || transducers::map(|x: usize| x * 2)).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_transduce_into_sorted_vec() {
+        let result = vec![3usize, 1, 4, 1, 5, 9].transduce_into_sorted_vec::<(), _, _, _>(transducers::identity()).unwrap();
+        assert_eq!(vec![1, 1, 3, 4, 5, 9], result);
+
+        let result = vec![3, 1, 4, 1, 5, 9].transduce_into_sorted_vec_by::<(), _, _, _, _>(transducers::map(|x: usize| x * 2),
+                                                                        |a, b| b.cmp(a)).unwrap();
+        assert_eq!(vec![18, 10, 8, 6, 2, 2], result);
+
+        let words = vec!["ccc", "a", "BB", "dd"];
+        let result = words.transduce_into_sorted_vec_by_key::<(), _, _, _, _, _>(transducers::map(|s: &str| s.to_lowercase()),
+                                                            |s| s.len()).unwrap();
+        assert_eq!(vec!["a", "bb", "dd", "ccc"], result);
+    }
+
+    #[test]
+    fn test_transduce_str() {
+        let transducer = super::compose(transducers::map(|c: char| c.to_ascii_uppercase()),
+                                        transducers::filter(|c: &char| !c.is_whitespace()));
+        let result: String = "Hello World".transduce_chars(transducer).collect();
+        assert_eq!("HELLOWORLD", result);
+
+        let transducer = super::compose(transducers::map(|c: char| c.to_ascii_uppercase()),
+                                        transducers::filter(|c: &char| !c.is_whitespace()));
+        assert_eq!(Ok(String::from("HELLOWORLD")), String::from("Hello World").transduce_into_string(transducer));
+
+        let result: Vec<u8> = "héllo".transduce_bytes(transducers::filter(|b: &u8| b.is_ascii())).collect();
+        assert_eq!(b"hllo".to_vec(), result);
+    }
+
+    #[test]
+    fn test_transduce_into_array() {
+        let source = vec![1, 2, 3, 4, 5];
+        let result = source.clone().transduce_into_array::<3, (), _, _, _>(transducers::take(3)).unwrap();
+        assert_eq!(Some([1, 2, 3]), result);
+
+        let result: Option<[usize; 3]> = vec![1, 2].transduce_into_array::<3, (), _, _, _>(transducers::take(3)).unwrap();
+        assert_eq!(None, result);
+
+        let result: Option<[usize; 3]> = source.transduce_into_array::<3, (), _, _, _>(transducers::identity()).unwrap();
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn test_transduce_join() {
+        let result = vec![1, 2, 3].transduce_join(", ", transducers::map(|x: usize| x)).unwrap();
+        assert_eq!("1, 2, 3", result);
+
+        let result = vec![1usize].transduce_join(", ", transducers::identity()).unwrap();
+        assert_eq!("1", result);
+
+        let result = Vec::<usize>::new().transduce_join(", ", transducers::identity()).unwrap();
+        assert_eq!("", result);
+    }
+
+    #[test]
+    fn test_transduce_drain() {
+        let mut source = vec![1, 2, 3, 4, 5, 6];
+        let expected_result = source.clone().transduce_into::<(), _, _, _>(transducers::filter(|x: &usize| *x > 3)).unwrap();
+        let result = source.transduce_drain::<(), _, _, _>(transducers::filter(|x: &usize| *x > 3)).unwrap();
+        assert_eq!(expected_result, result);
+        assert!(source.is_empty());
+
+        source.extend(vec![7, 8, 9]);
+        let result = source.transduce_drain::<(), _, _, _>(transducers::take(1)).unwrap();
+        assert_eq!(vec![7], result);
+        assert!(source.is_empty());
+    }
+
+    #[test]
+    fn test_transduce_maps() {
+        let mut source = BTreeMap::new();
+        source.insert(3, "c");
+        source.insert(-1, "z");
+        source.insert(1, "a");
+        source.insert(2, "b");
+        let transducer = transducers::filter(|&(k, _): &(i32, &str)| k > 0);
+        let result = source.transduce_into(transducer).unwrap();
+        assert_eq!(vec![(1, "a"), (2, "b"), (3, "c")], result);
+
+        let mut source = HashMap::new();
+        source.insert("a", 1);
+        source.insert("b", 2);
+        source.insert("c", 3);
+        let transducer = transducers::map(|(_, v): (&str, usize)| v * 2);
+        let mut result = source.transduce_into(transducer).unwrap();
+        result.sort();
+        assert_eq!(vec![2, 4, 6], result);
+    }
+
+    #[test]
+    fn test_drain_and_close() {
+        let (mut tx, rx) = transducing_channel(transducers::partition_all(2));
+        for x in 1..4 {
+            tx.send(x).unwrap();
+        }
+        assert_eq!(vec![1, 2], rx.recv().unwrap());
+        tx.drain_and_close().unwrap();
+        assert_eq!(vec![vec![3]], rx.into_vec());
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let (mut tx, rx) = transducing_channel(transducers::map(|x: usize| x * 2));
+        let (resume_tx, resume_rx) = mpsc::channel();
+        let producer = thread::spawn(move || {
+            tx.send(1).unwrap();
+            tx.send(2).unwrap();
+            resume_rx.recv().unwrap();
+            tx.send(3).unwrap();
+        });
+        let timeout = Duration::from_millis(50);
+        let mut received = with_timeout(rx, timeout);
+        assert_eq!(Some(Ok(2)), received.next());
+        assert_eq!(Some(Ok(4)), received.next());
+        let result: Result<Vec<usize>, TimeoutError> = received.by_ref().collect();
+        assert_eq!(Err(TimeoutError { timeout }), result);
+        resume_tx.send(()).unwrap();
+        producer.join().unwrap();
+        let rest: Vec<Result<usize, TimeoutError>> = received.collect();
+        assert_eq!(vec![Ok(6)], rest);
+    }
+
+    #[test]
+    fn test_sink() {
+        {
+            let mut sink = Sink::new(vec![0], transducers::map(|x| x * 2));
+            let mut other = Vec::new();
+            for i in 1..4 {
+                assert!(matches!(sink.push(i), Ok(StepResult::Continue)));
+                other.push(i);
+            }
+            assert_eq!(vec![1, 2, 3], other);
+            assert_eq!(vec![0, 2, 4, 6], sink.finish().unwrap());
+        }
+        {
+            let mut sink = Sink::new(Vec::new(), transducers::take(2));
+            assert!(matches!(sink.push(1), Ok(StepResult::Continue)));
+            assert!(matches!(sink.push(2), Ok(StepResult::Stop)));
+            assert!(matches!(sink.push(3), Ok(StepResult::Stop)));
+            assert_eq!(vec![1, 2], sink.finish().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let source:Vec<usize> = (1..12).collect();
+        let expected_result = source.clone()
+            .transduce_into::<(), _, _, _>(super::compose(transducers::map(|x| x * 3),
+                                           transducers::filter(|x| x % 2 == 0)))
+            .unwrap();
+        for &chunk_size in &[1, 3, 11] {
+            let result = source.clone().transduce_chunks::<(), _, _, _, _>(chunk_size, || {
+                super::compose(transducers::map(|x| x * 3), transducers::filter(|x| x % 2 == 0))
+            }).unwrap();
+            assert_eq!(expected_result, result);
+        }
+        let result = source.clone().transduce_chunks::<(), _, _, _, _>(4, || transducers::partition_all(3)).unwrap();
+        let expected_result:Vec<Vec<usize>> = vec![vec![1, 2, 3], vec![4],
+                                                   vec![5, 6, 7], vec![8],
+                                                   vec![9, 10, 11]];
+        assert_eq!(expected_result, result);
+    }
+
+    struct SumReducer(usize);
+
+    impl Reducing<usize, usize, ()> for SumReducer {
+        type Item = usize;
+
+        fn step(&mut self, value: usize) -> Result<StepResult, ()> {
+            self.0 += value;
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_chunks_with_reducer() {
+        let source:Vec<usize> = (1..11).collect();
+        let result = source.transduce_chunks_with_reducer(3, || transducers::take(2), SumReducer(0))
+            .unwrap();
+        assert_eq!(1 + 2 + 4 + 5 + 7 + 8 + 10, result.0);
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let source = vec![1, 2, 3, 10, 11, 4, 4];
+        let transducer = transducers::coalesce(|a, b| if a + b < 10 {
+            Ok(a + b)
+        } else {
+            Err((a, b))
+        });
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![6, 10, 11, 8], result);
+
+        let source = vec![1, 2, 3, 4];
+        let transducer = transducers::coalesce(|a, b| if a + b <= 5 {
+            Ok(a + b)
+        } else {
+            Err((a, b))
+        });
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![3, 3, 4], result);
+
+        let result = Vec::<usize>::new().transduce_into::<(), _, _, _>(transducers::coalesce(|a, b| Err((a, b)))).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_transduce_tee() {
+        let collect = fn_reducer(Vec::new(), |acc: &mut Vec<usize>, x: usize| {
+            acc.push(x);
+            Ok::<_, ()>(StepResult::Continue)
+        });
+        let sum = fn_reducer(0, |acc: &mut usize, x: usize| {
+            *acc += x;
+            Ok(StepResult::Continue)
+        });
+        let transducer = transducers::map(|x: usize| x * 2);
+        let (collect, sum) = transduce_tee(vec![1, 2, 3], transducer, collect, sum).unwrap();
+        assert_eq!(vec![2, 4, 6], collect.finish());
+        assert_eq!(12, sum.finish());
+
+        let seen = Cell::new(0);
+        let first_two = fn_reducer(Vec::new(), |acc: &mut Vec<usize>, x: usize| {
+            acc.push(x);
+            Ok::<_, ()>(if acc.len() == 2 { StepResult::Stop } else { StepResult::Continue })
+        });
+        let first_three = fn_reducer(Vec::new(), |acc: &mut Vec<usize>, x: usize| {
+            acc.push(x);
+            Ok(if acc.len() == 3 { StepResult::Stop } else { StepResult::Continue })
+        });
+        let transducer = transducers::map(|x: usize| {
+            seen.set(seen.get() + 1);
+            x
+        });
+        let (first_two, first_three) = transduce_tee(1..10, transducer, first_two, first_three).unwrap();
+        assert_eq!(vec![1, 2], first_two.finish());
+        assert_eq!(vec![1, 2, 3], first_three.finish());
+        assert_eq!(3, seen.get());
+    }
+
+    #[test]
+    fn test_fn_reducer() {
+        let mut reducer = fn_reducer(Vec::new(), |acc, x| {
+            acc.push(x);
+            if acc.len() < 3 {
+                Ok::<_, ()>(StepResult::Continue)
+            } else {
+                Ok(StepResult::Stop)
+            }
+        });
+        drive(1..10, transducers::filter(|x| x % 2 == 0), &mut reducer).unwrap();
+        assert_eq!(vec![2, 4, 6], reducer.finish());
+    }
+
+    #[cfg(feature = "futures-sink")]
+    #[test]
+    fn test_futures_sink() {
+        use futures::executor::block_on;
+        use futures::{stream, SinkExt, StreamExt};
+
+        let (mut tx, rx) = transducing_channel(transducers::partition_all(2));
+        let mut values = stream::iter(vec![1, 2, 3, 4, 5]).map(Ok);
+        block_on(tx.send_all(&mut values)).unwrap();
+        block_on(SinkExt::close(&mut tx)).unwrap();
+        drop(tx);
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], rx.into_vec());
+    }
+
+    #[cfg(feature = "futures-sink")]
+    struct WakeFlag(std::sync::atomic::AtomicBool);
+
+    #[cfg(feature = "futures-sink")]
+    impl futures::task::ArcWake for WakeFlag {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[cfg(feature = "futures-sink")]
+    #[test]
+    fn test_futures_sink_bounded() {
+        use futures::executor::block_on;
+        use futures::{stream, Sink, SinkExt, StreamExt};
+
+        let (mut tx, rx) = bounded_transducing_channel(1, transducers::map(|x: usize| x * 2));
+        let flag = Arc::new(WakeFlag(std::sync::atomic::AtomicBool::new(false)));
+        let waker = futures::task::waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(Poll::Ready(Ok(())), Pin::new(&mut tx).poll_ready(&mut cx));
+        Pin::new(&mut tx).start_send(1).unwrap();
+        assert_eq!(Poll::Ready(Ok(())), Pin::new(&mut tx).poll_ready(&mut cx));
+        Pin::new(&mut tx).start_send(2).unwrap();
+        assert_eq!(Poll::Pending, Pin::new(&mut tx).poll_ready(&mut cx));
+        assert_eq!(Poll::Pending, Pin::new(&mut tx).poll_ready(&mut cx));
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        assert_eq!(2, rx.recv().unwrap());
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(Poll::Ready(Ok(())), Pin::new(&mut tx).poll_ready(&mut cx));
+        assert_eq!(4, rx.recv().unwrap());
+
+        let (mut tx, rx) = bounded_transducing_channel(1, transducers::partition_all(2));
+        let consumer = thread::spawn(move || rx.into_vec());
+        let mut values = stream::iter(vec![1, 2, 3, 4, 5]).map(Ok);
+        block_on(tx.send_all(&mut values)).unwrap();
+        block_on(SinkExt::close(&mut tx)).unwrap();
+        drop(tx);
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], consumer.join().unwrap());
+    }
+
+    #[cfg(feature = "futures-stream")]
+    #[test]
+    fn test_futures_stream() {
+        use futures::executor::block_on;
+        use futures::StreamExt;
+        use super::applications::futures_stream::transducing_stream;
+
+        let (mut tx, stream) = transducing_stream(transducers::partition_all(2));
+        let producer = thread::spawn(move || {
+            for x in 1..6 {
+                tx.send(x).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            }
+            tx.close().unwrap();
+        });
+        let result = block_on(stream.collect::<Vec<_>>());
+        producer.join().unwrap();
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], result);
+
+        let (mut tx, stream) = transducing_stream(transducers::map(|x: usize| x));
+        drop(stream);
+        assert!(tx.send(1).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Serialize, Deserialize)]
+    struct CollectReducer(Vec<usize>);
+
+    #[cfg(feature = "serde")]
+    impl Reducing<usize, Vec<usize>, ()> for CollectReducer {
+        type Item = usize;
+
+        fn step(&mut self, value: usize) -> Result<StepResult, ()> {
+            self.0.push(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checkpoint() {
+        use serde_json;
+        use super::Transducer;
+        use super::applications::checkpoint::{Checkpoint, restore_from_snapshot};
+        use super::transducers::TakeReducer;
+
+        let mut rf = Checkpoint::new(2, transducers::take(3).new(CollectReducer(Vec::new())));
+        assert!(matches!(rf.step(1), Ok(StepResult::Continue)));
+        assert!(rf.snapshot().is_none());
+        assert!(matches!(rf.step(2), Ok(StepResult::Continue)));
+        let snapshot = rf.snapshot().unwrap().to_string();
+        drop(rf);
+
+        let mut restored:TakeReducer<CollectReducer> = restore_from_snapshot(&snapshot).unwrap();
+        assert!(matches!(restored.step(3), Ok(StepResult::Stop)));
+        restored.complete().unwrap();
+        let state = serde_json::to_value(&restored).unwrap();
+        assert_eq!(serde_json::json!([1, 2, 3]), state["rf"]);
+    }
+
+    #[cfg(feature = "serde")]
+    struct LimitedReducer(Vec<usize>);
+
+    #[cfg(feature = "serde")]
+    impl Serialize for LimitedReducer {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where S: serde::Serializer {
+            if self.0.len() > 2 {
+                return Err(<S::Error as serde::ser::Error>::custom("too many values"));
+            }
+            self.0.serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl Reducing<usize, Vec<usize>, ()> for LimitedReducer {
+        type Item = usize;
+
+        fn step(&mut self, value: usize) -> Result<StepResult, ()> {
+            self.0.push(value);
+            Ok(StepResult::Continue)
+        }
+
+        fn complete(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checkpoint_error() {
+        use super::applications::checkpoint::Checkpoint;
+
+        let mut rf = Checkpoint::new(1, LimitedReducer(Vec::new()));
+        rf.step(1).unwrap();
+        rf.step(2).unwrap();
+        assert_eq!(Some("[1,2]"), rf.snapshot());
+        assert!(rf.snapshot_error().is_none());
+
+        rf.step(3).unwrap();
+        assert_eq!(Some("[1,2]"), rf.snapshot());
+        assert!(rf.snapshot_error().is_some());
+    }
+
+    #[test]
+    fn test_identity() {
+        let source = vec![1, 2, 3];
+        let result = source.transduce_into::<(), _, _, _>(transducers::identity()).unwrap();
+        assert_eq!(vec![1, 2, 3], result);
+    }
+
+    #[cfg(feature = "trace")]
+    struct CaptureLogger(Mutex<Vec<String>>);
+
+    #[cfg(feature = "trace")]
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push(format!("{}", record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[cfg(feature = "trace")]
+    static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_trace() {
+        #[cfg(feature = "trace")]
+        {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        }
+        let source = vec![1, 2, 3];
+        let trace: transducers::TraceTransducer = transducers::trace_transducer("input");
+        let transducer = super::compose(transducers::take(2), trace);
+        let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+        assert_eq!(vec![1, 2], result);
+        #[cfg(feature = "trace")]
+        assert_eq!(vec!["input: 1 -> continue", "input: 2 -> stop", "input: complete"],
+                   *LOGGER.0.lock().unwrap());
+    }
+
+    #[cfg(not(feature = "trace"))]
+    #[test]
+    fn test_trace_without_debug() {
+        #[derive(PartialEq)]
+        struct Opaque(usize);
+
+        let source = vec![Opaque(1), Opaque(2)];
+        let result = source.transduce_into::<(), _, _, _>(transducers::trace_transducer("input")).unwrap();
+        assert!(result == vec![Opaque(1), Opaque(2)]);
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metric(snapshotter: &Snapshotter, name: &str) -> DebugValue {
+        snapshotter.snapshot().into_vec().into_iter()
+            .find(|(key, _, _, _)| key.key().name() == name)
+            .map(|(_, _, _, value)| value)
+            .unwrap()
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let source = vec![1, 2, 3, 4, 5];
+            let transducer = super::compose(transducers::filter(|x| x % 2 == 0),
+                                            transducers::metrics("input"));
+            let result = source.transduce_into::<(), _, _, _>(transducer).unwrap();
+            assert_eq!(vec![2, 4], result);
+
+            let (mut tx, _rx) = bounded_transducing_channel(1, transducers::metrics("sent"));
+            tx.send(1).unwrap();
+            assert!(tx.try_send(2).is_err());
+        });
+        assert_eq!(DebugValue::Counter(5), metric(&snapshotter, "input"));
+        assert_eq!(DebugValue::Counter(0), metric(&snapshotter, "input.errors"));
+        assert_eq!(DebugValue::Counter(1), metric(&snapshotter, "input.completions"));
+        assert_eq!(DebugValue::Counter(2), metric(&snapshotter, "sent"));
+        assert_eq!(DebugValue::Counter(1), metric(&snapshotter, "sent.errors"));
+        assert_eq!(DebugValue::Counter(0), metric(&snapshotter, "sent.completions"));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_gauge() {
+        use metrics_util::debugging::DebugValue::Gauge;
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let (mut tx, rx) = metrics::with_local_recorder(&recorder, || {
+            metrics_gauge("depth", 2, transducers::mapcat(|x: usize| vec![x, x]))
+        });
+        assert_eq!(Gauge(0.0.into()), metric(&snapshotter, "depth"));
+        tx.send(1).unwrap();
+        assert_eq!(Gauge(2.0.into()), metric(&snapshotter, "depth"));
+        assert_eq!(1, rx.recv().unwrap());
+        assert_eq!(Gauge(1.0.into()), metric(&snapshotter, "depth"));
+        assert!(tx.try_send(2).is_err());
+        assert_eq!(Gauge(2.0.into()), metric(&snapshotter, "depth"));
+        assert_eq!(vec![1, 2], rx.take(2).collect::<Vec<_>>());
+        assert_eq!(Gauge(0.0.into()), metric(&snapshotter, "depth"));
+    }
 }
@@ -7,13 +7,49 @@
  * option. This file may not be copied, modified, or distributed
  * except according to those terms.
  */
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::collections::BTreeMap;
+use alloc::collections::VecDeque;
+use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "trace")]
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::iter::Peekable;
+use core::marker::PhantomData;
+use core::mem;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::hash::Hash;
-use std::marker::PhantomData;
-use std::mem;
-
-use super::{Transducer, Reducing, StepResult};
-
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "std")]
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+#[cfg(feature = "trace")]
+use log::Level;
+#[cfg(feature = "metrics")]
+use metrics::Counter;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use super::{Transducer, Reducing, Stateless, StepResult};
+
+#[must_use = "transducers do nothing unless applied"]
 pub struct MapTransducer<F> {
     f: F
 }
@@ -54,38 +90,49 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for MapReducer<R, F>
     }
 }
 
+impl<F, I, O> Stateless<I> for MapTransducer<F>
+    where F: Fn(I) -> O {
+
+    type Output = O;
+
+    #[inline]
+    fn apply_one(&self, value: I) -> O {
+        (self.f)(value)
+    }
+}
+
 pub fn map<F, I, O>(f: F) -> MapTransducer<F>
     where F: Fn(I) -> O {
 
     MapTransducer {
-        f: f
+        f
     }
 }
 
-pub struct MapIndexedTransducer<F> {
+#[must_use = "transducers do nothing unless applied"]
+pub struct ContextualTransducer<C, F> {
+    ctx: Arc<C>,
     f: F
 }
 
-pub struct MapIndexedReducer<R, F> {
+pub struct ContextualReducer<R, C, F> {
     rf: R,
-    t: MapIndexedTransducer<F>,
-    count: usize
+    t: ContextualTransducer<C, F>
 }
 
-impl<F, RI> Transducer<RI> for MapIndexedTransducer<F> {
-    type RO = MapIndexedReducer<RI, F>;
+impl<C, F, RI> Transducer<RI> for ContextualTransducer<C, F> {
+    type RO = ContextualReducer<RI, C, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        MapIndexedReducer {
+        ContextualReducer {
             rf: reducing_fn,
-            t: self,
-            count: 0
+            t: self
         }
     }
 }
 
-impl<R, F, I, O, OF, E> Reducing<I, OF, E> for MapIndexedReducer<R, F>
-    where F: Fn(usize, I) -> O,
+impl<R, C, F, I, O, OF, E> Reducing<I, OF, E> for ContextualReducer<R, C, F>
+    where F: Fn(&C, I) -> O,
           R: Reducing<O, OF, E> {
 
     type Item = O;
@@ -96,9 +143,7 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for MapIndexedReducer<R, F>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        let idx = self.count;
-        self.count += 1;
-        self.rf.step((self.t.f)(idx, value))
+        self.rf.step((self.t.f)(&self.t.ctx, value))
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -106,55 +151,50 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for MapIndexedReducer<R, F>
     }
 }
 
-pub fn map_indexed<F, I, O>(f: F) -> MapIndexedTransducer<F>
-    where F: Fn(usize, I) -> O {
+/// As `map`, but the function is also given a context shared through an
+/// `Arc`, so several stages of a pipeline can share the same state
+pub fn contextual_map<C, F, I, O>(ctx: Arc<C>, f: F) -> ContextualTransducer<C, F>
+    where F: Fn(&C, I) -> O {
 
-    MapIndexedTransducer {
-        f: f
+    ContextualTransducer {
+        ctx,
+        f
     }
 }
 
-pub struct MapcatTransducer<F> {
-    f: F
-}
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapInPlaceTransducer<F>(F);
 
-pub struct MapcatReducer<R, F> {
+pub struct MapInPlaceReducer<R, F> {
     rf: R,
-    t: MapcatTransducer<F>
+    t: MapInPlaceTransducer<F>
 }
 
-impl<F, RI> Transducer<RI> for MapcatTransducer<F> {
-    type RO = MapcatReducer<RI, F>;
+impl<F, RI> Transducer<RI> for MapInPlaceTransducer<F> {
+    type RO = MapInPlaceReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        MapcatReducer {
+        MapInPlaceReducer {
             rf: reducing_fn,
             t: self
         }
     }
 }
 
-impl<R, F, I, O, IO, OF, E> Reducing<I, OF, E> for MapcatReducer<R, F>
-    where IO: IntoIterator<Item=O>,
-          F: Fn(I) -> IO,
-          R: Reducing<O, OF, E> {
+impl<R, F, I, OF, E> Reducing<I, OF, E> for MapInPlaceReducer<R, F>
+    where F: Fn(&mut I),
+          R: Reducing<I, OF, E> {
 
-    type Item = O;
+    type Item = I;
 
     fn init(&mut self) {
         self.rf.init();
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        for o in (self.t.f)(value) {
-            match self.rf.step(o) {
-                Ok(StepResult::Continue) => (),
-                Ok(StepResult::Stop) => return Ok(StepResult::Stop),
-                Err(e) => return Err(e)
-            }
-        }
-        Ok(StepResult::Continue)
+    fn step(&mut self, mut value: I) -> Result<StepResult, E> {
+        (self.t.0)(&mut value);
+        self.rf.step(value)
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -162,56 +202,46 @@ impl<R, F, I, O, IO, OF, E> Reducing<I, OF, E> for MapcatReducer<R, F>
     }
 }
 
-pub fn mapcat<F, I, O, IO>(f: F) -> MapcatTransducer<F>
-    where IO: IntoIterator<Item=O>,
-          F: Fn(I) -> IO {
+/// Mutates each value in place before passing it on, for changes that
+/// would otherwise mean building a new value
+pub fn map_in_place<F, I>(f: F) -> MapInPlaceTransducer<F>
+    where F: Fn(&mut I) {
 
-    MapcatTransducer {
-        f: f
-    }
+    MapInPlaceTransducer(f)
 }
 
-pub struct FilterTransducer<F> {
-    f: F,
-    inclusive: bool
-}
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapKeysTransducer<F>(F);
 
-pub struct FilterReducer<R, F> {
+pub struct MapKeysReducer<R, F> {
     rf: R,
-    t: FilterTransducer<F>
+    t: MapKeysTransducer<F>
 }
 
-impl<F, RI> Transducer<RI> for FilterTransducer<F> {
-    type RO = FilterReducer<RI, F>;
+impl<F, RI> Transducer<RI> for MapKeysTransducer<F> {
+    type RO = MapKeysReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        FilterReducer {
+        MapKeysReducer {
             rf: reducing_fn,
             t: self
         }
     }
 }
 
-impl<R, F, I, OF, E> Reducing<I, OF, E> for FilterReducer<R, F>
-    where F: Fn(&I) -> bool,
-          R: Reducing<I, OF, E> {
-    type Item = I;
+impl<R, F, K, KO, V, OF, E> Reducing<(K, V), OF, E> for MapKeysReducer<R, F>
+    where F: Fn(K) -> KO,
+          R: Reducing<(KO, V), OF, E> {
+
+    type Item = (KO, V);
 
     fn init(&mut self) {
         self.rf.init();
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        let mut include = (self.t.f)(&value);
-        if !self.t.inclusive {
-            include = !include;
-        }
-        if include {
-            self.rf.step(value)
-        } else {
-            Ok(StepResult::Continue)
-        }
+    fn step(&mut self, (k, v): (K, V)) -> Result<StepResult, E> {
+        self.rf.step(((self.t.0)(k), v))
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -219,58 +249,44 @@ impl<R, F, I, OF, E> Reducing<I, OF, E> for FilterReducer<R, F>
     }
 }
 
-pub fn filter<F, T>(f: F) -> FilterTransducer<F>
-    where F: Fn(&T) -> bool {
-
-    FilterTransducer {
-        f: f,
-        inclusive: true
-    }
-}
-
-pub fn remove<F, T>(f: F) -> FilterTransducer<F>
-    where F: Fn(&T) -> bool {
+pub fn map_keys<F, K, KO>(f: F) -> MapKeysTransducer<F>
+    where F: Fn(K) -> KO {
 
-    FilterTransducer {
-        f: f,
-        inclusive: false
-    }
+    MapKeysTransducer(f)
 }
 
-pub struct KeepTransducer<F>(F);
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapValuesTransducer<F>(F);
 
-pub struct KeepReducer<R, F> {
+pub struct MapValuesReducer<R, F> {
     rf: R,
-    t: KeepTransducer<F>
+    t: MapValuesTransducer<F>
 }
 
-impl<F, RI> Transducer<RI> for KeepTransducer<F> {
-    type RO = KeepReducer<RI, F>;
+impl<F, RI> Transducer<RI> for MapValuesTransducer<F> {
+    type RO = MapValuesReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        KeepReducer {
+        MapValuesReducer {
             rf: reducing_fn,
             t: self
         }
     }
 }
 
-impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepReducer<R, F>
-    where F: Fn(I) -> Option<O>,
-          R: Reducing<O, OF, E> {
+impl<R, F, K, V, VO, OF, E> Reducing<(K, V), OF, E> for MapValuesReducer<R, F>
+    where F: Fn(V) -> VO,
+          R: Reducing<(K, VO), OF, E> {
 
-    type Item = O;
+    type Item = (K, VO);
 
     fn init(&mut self) {
         self.rf.init();
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        match (self.t.0)(value) {
-            Some(o) => self.rf.step(o),
-            None => Ok(StepResult::Continue)
-        }
+    fn step(&mut self, (k, v): (K, V)) -> Result<StepResult, E> {
+        self.rf.step((k, (self.t.0)(v)))
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -278,34 +294,34 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepReducer<R, F>
     }
 }
 
-pub fn keep<F, I, O>(f: F) -> KeepTransducer<F>
-    where F: Fn(I) -> Option<O> {
+pub fn map_values<F, V, VO>(f: F) -> MapValuesTransducer<F>
+    where F: Fn(V) -> VO {
 
-    KeepTransducer(f)
+    MapValuesTransducer(f)
 }
 
-pub struct KeepIndexedTransducer<F>(F);
+#[must_use = "transducers do nothing unless applied"]
+pub struct TryMapTransducer<F>(F);
 
-pub struct KeepIndexedReducer<R, F> {
+pub struct TryMapReducer<R, F> {
     rf: R,
-    t: KeepIndexedTransducer<F>,
-    count: usize
+    t: TryMapTransducer<F>
 }
 
-impl<F, RI> Transducer<RI> for KeepIndexedTransducer<F> {
-    type RO = KeepIndexedReducer<RI, F>;
+impl<F, RI> Transducer<RI> for TryMapTransducer<F> {
+    type RO = TryMapReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        KeepIndexedReducer {
+        TryMapReducer {
             rf: reducing_fn,
-            t: self,
-            count: 0
+            t: self
         }
     }
 }
 
-impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepIndexedReducer<R, F>
-    where F: Fn(usize, I) -> Option<O>,
+impl<R, F, I, O, FE, OF, E> Reducing<I, OF, E> for TryMapReducer<R, F>
+    where F: Fn(I) -> Result<O, FE>,
+          E: From<FE>,
           R: Reducing<O, OF, E> {
 
     type Item = O;
@@ -316,13 +332,7 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepIndexedReducer<R, F>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        let idx = self.count;
-        self.count += 1;
-
-        match (self.t.0)(idx, value) {
-            Some(o) => self.rf.step(o),
-            None => Ok(StepResult::Continue)
-        }
+        self.rf.step((self.t.0)(value)?)
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -330,41 +340,40 @@ impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepIndexedReducer<R, F>
     }
 }
 
-pub fn keep_indexed<F, I, O>(f: F) -> KeepIndexedTransducer<F>
-    where F: Fn(usize, I) -> Option<O> {
+pub fn try_map<F, I, O, E>(f: F) -> TryMapTransducer<F>
+    where F: Fn(I) -> Result<O, E> {
 
-    KeepIndexedTransducer(f)
+    TryMapTransducer(f)
 }
 
-pub struct PartitionTransducer<T> {
-    size: usize,
-    all: bool,
-    t: PhantomData<T>
+#[must_use = "transducers do nothing unless applied"]
+pub struct RetryMapTransducer<F> {
+    attempts: usize,
+    f: F
 }
 
-pub struct PartitionReducer<RF, T> {
-    t: PartitionTransducer<T>,
-    rf: RF,
-    holder: Vec<T>
+pub struct RetryMapReducer<R, F> {
+    rf: R,
+    t: RetryMapTransducer<F>
 }
 
-impl<RI, T> Transducer<RI> for PartitionTransducer<T> {
-    type RO = PartitionReducer<RI, T>;
+impl<F, RI> Transducer<RI> for RetryMapTransducer<F> {
+    type RO = RetryMapReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        let size = self.size;
-        PartitionReducer {
-            t: self,
+        RetryMapReducer {
             rf: reducing_fn,
-            holder: Vec::with_capacity(size)
+            t: self
         }
     }
 }
 
-impl<R, I, OF, E> Reducing<I, OF, E> for PartitionReducer<R, I>
-    where R: Reducing<Vec<I>, OF, E> {
+impl<R, F, I, O, FE, OF, E> Reducing<I, OF, E> for RetryMapReducer<R, F>
+    where F: Fn(&I) -> Result<O, FE>,
+          E: From<FE>,
+          R: Reducing<O, OF, E> {
 
-    type Item = Vec<I>;
+    type Item = O;
 
     fn init(&mut self) {
         self.rf.init();
@@ -372,64 +381,53 @@ impl<R, I, OF, E> Reducing<I, OF, E> for PartitionReducer<R, I>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        self.holder.push(value);
-        if self.holder.len() == self.t.size {
-            let mut other_holder = Vec::with_capacity(self.t.size);
-            mem::swap(&mut other_holder, &mut self.holder);
-            self.rf.step(other_holder)
-        } else {
-            Ok(StepResult::Continue)
+        let mut result = (self.t.f)(&value);
+        for _ in 1..self.t.attempts {
+            if result.is_ok() {
+                break;
+            }
+            result = (self.t.f)(&value);
         }
+        self.rf.step(result?)
     }
 
     fn complete(&mut self) -> Result<(), E> {
-        if self.t.all {
-            let mut other_holder = Vec::new();
-            mem::swap(&mut other_holder, &mut self.holder);
-            try!(self.rf.step(other_holder));
-        }
         self.rf.complete()
     }
 }
 
-pub fn partition<T>(num: usize) -> PartitionTransducer<T> {
-    PartitionTransducer {
-        size: num,
-        all: false,
-        t: PhantomData
-    }
-}
+/// As `try_map`, but `f` is called up to `attempts` times for each value
+/// until it succeeds, only the last error is passed on
+pub fn retry_map<F, I, O, E>(attempts: usize, f: F) -> RetryMapTransducer<F>
+    where F: Fn(&I) -> Result<O, E> {
 
-pub fn partition_all<T>(num: usize) -> PartitionTransducer<T> {
-    PartitionTransducer {
-        size: num,
-        all: true,
-        t: PhantomData
+    assert!(attempts > 0, "retry_map requires at least one attempt");
+    RetryMapTransducer {
+        attempts,
+        f
     }
 }
 
-pub struct TakeTransducer(usize);
+#[must_use = "transducers do nothing unless applied"]
+pub struct FlattenResultsTransducer;
 
-pub struct TakeReducer<RF> {
-    rf: RF,
-    taken: usize,
-    t: TakeTransducer
+pub struct FlattenResultsReducer<R> {
+    rf: R
 }
 
-impl<RI> Transducer<RI> for TakeTransducer {
-    type RO = TakeReducer<RI>;
+impl<RI> Transducer<RI> for FlattenResultsTransducer {
+    type RO = FlattenResultsReducer<RI>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        TakeReducer {
-            rf: reducing_fn,
-            taken: 0,
-            t: self
+        FlattenResultsReducer {
+            rf: reducing_fn
         }
     }
 }
 
-impl<R, I, OF, E> Reducing<I, OF, E> for TakeReducer<R>
-    where R: Reducing<I, OF, E> {
+impl<R, I, FE, OF, E> Reducing<Result<I, FE>, OF, E> for FlattenResultsReducer<R>
+    where E: From<FE>,
+          R: Reducing<I, OF, E> {
 
     type Item = I;
 
@@ -438,21 +436,8 @@ impl<R, I, OF, E> Reducing<I, OF, E> for TakeReducer<R>
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if self.taken < self.t.0 {
-            self.taken += 1;
-            match self.rf.step(value) {
-                Ok(StepResult::Continue) => if self.taken < self.t.0 {
-                    Ok(StepResult::Continue)
-                } else {
-                    Ok(StepResult::Stop)
-                },
-                Ok(StepResult::Stop) => Ok(StepResult::Stop),
-                Err(e) => Err(e)
-            }
-        } else {
-            Ok(StepResult::Stop)
-        }
+    fn step(&mut self, value: Result<I, FE>) -> Result<StepResult, E> {
+        self.rf.step(value?)
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -460,31 +445,31 @@ impl<R, I, OF, E> Reducing<I, OF, E> for TakeReducer<R>
     }
 }
 
-pub fn take(num: usize) -> TakeTransducer {
-    TakeTransducer(num)
+/// For values that are `Result<I, FE>`, passes on each `Ok` value and
+/// aborts with the first error, converted with `From`
+pub fn flatten_results() -> FlattenResultsTransducer {
+    FlattenResultsTransducer
 }
 
-pub struct TakeWhileTransducer<F>(F);
+#[must_use = "transducers do nothing unless applied"]
+pub struct FlattenResultsSkipTransducer;
 
-pub struct TakeWhileReducer<RF, F> {
-    rf: RF,
-    t: TakeWhileTransducer<F>
+pub struct FlattenResultsSkipReducer<R> {
+    rf: R
 }
 
-impl<RI, F> Transducer<RI> for TakeWhileTransducer<F> {
-    type RO = TakeWhileReducer<RI, F>;
+impl<RI> Transducer<RI> for FlattenResultsSkipTransducer {
+    type RO = FlattenResultsSkipReducer<RI>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        TakeWhileReducer {
-            rf: reducing_fn,
-            t: self
+        FlattenResultsSkipReducer {
+            rf: reducing_fn
         }
     }
 }
 
-impl<R, I, OF, E, F> Reducing<I, OF, E> for TakeWhileReducer<R, F>
-    where R: Reducing<I, OF, E>,
-          F: Fn(&I) -> bool {
+impl<R, I, FE, OF, E> Reducing<Result<I, FE>, OF, E> for FlattenResultsSkipReducer<R>
+    where R: Reducing<I, OF, E> {
 
     type Item = I;
 
@@ -493,11 +478,10 @@ impl<R, I, OF, E, F> Reducing<I, OF, E> for TakeWhileReducer<R, F>
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if (self.t.0)(&value) {
-            self.rf.step(value)
-        } else {
-            Ok(StepResult::Stop)
+    fn step(&mut self, value: Result<I, FE>) -> Result<StepResult, E> {
+        match value {
+            Ok(v) => self.rf.step(v),
+            Err(_) => Ok(StepResult::Continue)
         }
     }
 
@@ -506,35 +490,36 @@ impl<R, I, OF, E, F> Reducing<I, OF, E> for TakeWhileReducer<R, F>
     }
 }
 
-pub fn take_while<F, T>(pred: F) -> TakeWhileTransducer<F>
-    where F: Fn(&T) -> bool {
-
-    TakeWhileTransducer(pred)
+/// As `flatten_results`, but errors are skipped
+pub fn flatten_results_skip() -> FlattenResultsSkipTransducer {
+    FlattenResultsSkipTransducer
 }
 
-pub struct DropWhileTransducer<F>(F);
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapErrTransducer<F, E> {
+    f: F,
+    e: PhantomData<E>
+}
 
-pub struct DropWhileReducer<RF, F> {
-    rf: RF,
-    t: DropWhileTransducer<F>,
-    done: bool
+pub struct MapErrReducer<R, F, E> {
+    rf: R,
+    t: MapErrTransducer<F, E>
 }
 
-impl<RI, F> Transducer<RI> for DropWhileTransducer<F> {
-    type RO = DropWhileReducer<RI, F>;
+impl<RI, F, E> Transducer<RI> for MapErrTransducer<F, E> {
+    type RO = MapErrReducer<RI, F, E>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        DropWhileReducer {
+        MapErrReducer {
             rf: reducing_fn,
-            t: self,
-            done: false
+            t: self
         }
     }
 }
 
-impl<R, I, OF, E, F> Reducing<I, OF, E> for DropWhileReducer<R, F>
-    where R: Reducing<I, OF, E>,
-          F: Fn(&I) -> bool {
+impl<R, F, I, OF, E1, E2> Reducing<I, OF, E2> for MapErrReducer<R, F, E1>
+    where F: Fn(E1) -> E2,
+          R: Reducing<I, OF, E1> {
 
     type Item = I;
 
@@ -543,16 +528,64 @@ impl<R, I, OF, E, F> Reducing<I, OF, E> for DropWhileReducer<R, F>
     }
 
     #[inline]
-    fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if self.done {
-            self.rf.step(value)
-        } else {
-            if !(self.t.0)(&value) {
-                self.done = true;
-                self.rf.step(value)
-            } else {
-                Ok(StepResult::Continue)
-            }
+    fn step(&mut self, value: I) -> Result<StepResult, E2> {
+        self.rf.step(value).map_err(&self.t.f)
+    }
+
+    fn complete(&mut self) -> Result<(), E2> {
+        self.rf.complete().map_err(&self.t.f)
+    }
+}
+
+/// Converts the errors of the reducing function it is applied to with `f`,
+/// so the transducers composed ahead of it see the converted error type
+pub fn map_err<F, E1, E2>(f: F) -> MapErrTransducer<F, E1>
+    where F: Fn(E1) -> E2 {
+
+    MapErrTransducer {
+        f,
+        e: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct RecoverWithTransducer<F>(F);
+
+pub struct RecoverWithReducer<R, F> {
+    rf: R,
+    t: RecoverWithTransducer<F>
+}
+
+impl<RI, F> Transducer<RI> for RecoverWithTransducer<F> {
+    type RO = RecoverWithReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        RecoverWithReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, F, I, OF, E> Reducing<I, OF, E> for RecoverWithReducer<R, F>
+    where F: Fn(&E) -> Option<I>,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        match self.rf.step(value) {
+            Err(e) => match (self.t.0)(&e) {
+                // An error from the fallback itself is passed on as-is
+                Some(fallback) => self.rf.step(fallback),
+                None => Err(e)
+            },
+            result => result
         }
     }
 
@@ -561,33 +594,45 @@ impl<R, I, OF, E, F> Reducing<I, OF, E> for DropWhileReducer<R, F>
     }
 }
 
-pub fn drop_while<F, T>(pred: F) -> DropWhileTransducer<F>
-    where F: Fn(&T) -> bool {
+/// Handles errors from the reducing function it is applied to: `f` may
+/// give a fallback value to step in place of the failed one, or `None` to
+/// pass the error on
+pub fn recover_with<F, I, E>(f: F) -> RecoverWithTransducer<F>
+    where F: Fn(&E) -> Option<I> {
 
-    DropWhileTransducer(pred)
+    RecoverWithTransducer(f)
 }
 
-pub struct DropTransducer(usize);
+/// Receives the number of errors swallowed by `count_errors`
+#[derive(Clone)]
+pub struct ErrorCount(Arc<AtomicUsize>);
 
-pub struct DropReducer<RF> {
-    rf: RF,
-    dropped: usize,
-    d: DropTransducer
+impl ErrorCount {
+    pub fn get(&self) -> usize {
+        self.0.load(AtomicOrdering::Relaxed)
+    }
 }
 
-impl<RI> Transducer<RI> for DropTransducer {
-    type RO = DropReducer<RI>;
+#[must_use = "transducers do nothing unless applied"]
+pub struct CountErrorsTransducer(ErrorCount);
+
+pub struct CountErrorsReducer<R> {
+    rf: R,
+    count: ErrorCount
+}
+
+impl<RI> Transducer<RI> for CountErrorsTransducer {
+    type RO = CountErrorsReducer<RI>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        DropReducer {
+        CountErrorsReducer {
             rf: reducing_fn,
-            dropped: 0,
-            d: self
+            count: self.0
         }
     }
 }
 
-impl<R, I, OF, E> Reducing<I, OF, E> for DropReducer<R>
+impl<R, I, OF, E> Reducing<I, OF, E> for CountErrorsReducer<R>
     where R: Reducing<I, OF, E> {
 
     type Item = I;
@@ -598,44 +643,53 @@ impl<R, I, OF, E> Reducing<I, OF, E> for DropReducer<R>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if self.dropped < self.d.0 {
-            self.dropped += 1;
-            Ok(StepResult::Continue)
-        } else {
-            self.rf.step(value)
+        match self.rf.step(value) {
+            Err(_) => {
+                self.count.0.fetch_add(1, AtomicOrdering::Relaxed);
+                Ok(StepResult::Continue)
+            },
+            result => result
         }
     }
 
     fn complete(&mut self) -> Result<(), E> {
-        self.rf.complete()
+        if self.rf.complete().is_err() {
+            self.count.0.fetch_add(1, AtomicOrdering::Relaxed);
+        }
+        Ok(())
     }
 }
 
-pub fn drop(size: usize) -> DropTransducer {
-    DropTransducer(size)
+/// Swallows errors from the reducing function it is applied to, counting
+/// them in the returned `ErrorCount` rather than aborting
+pub fn count_errors() -> (CountErrorsTransducer, ErrorCount) {
+    let count = ErrorCount(Arc::new(AtomicUsize::new(0)));
+    (CountErrorsTransducer(count.clone()), count)
 }
 
-pub struct ReplaceTransducer<T>(HashMap<T, T>);
+#[must_use = "transducers do nothing unless applied"]
+pub struct LimitErrorsTransducer(usize);
 
-pub struct ReplaceReducer<RF, T> {
-    rf: RF,
-    t: ReplaceTransducer<T>
+pub struct LimitErrorsReducer<R> {
+    rf: R,
+    max: usize,
+    errors: usize
 }
 
-impl <'a, RI, T> Transducer<RI> for ReplaceTransducer<T> {
-    type RO = ReplaceReducer<RI, T>;
+impl<RI> Transducer<RI> for LimitErrorsTransducer {
+    type RO = LimitErrorsReducer<RI>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        ReplaceReducer {
+        LimitErrorsReducer {
             rf: reducing_fn,
-            t: self
+            max: self.0,
+            errors: 0
         }
     }
 }
 
-impl<'a, R, I, OF, E> Reducing<I, OF, E> for ReplaceReducer<R, I>
-    where I: Eq + Hash + Clone,
-          R: Reducing<I, OF, E> {
+impl<R, I, OF, E> Reducing<I, OF, E> for LimitErrorsReducer<R>
+    where R: Reducing<I, OF, E> {
 
     type Item = I;
 
@@ -645,11 +699,13 @@ impl<'a, R, I, OF, E> Reducing<I, OF, E> for ReplaceReducer<R, I>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        let v:I = match self.t.0.get(&value) {
-            Some(val) => val.clone(),
-            None => value
-        };
-        self.rf.step(v)
+        match self.rf.step(value) {
+            Err(_) if self.errors < self.max => {
+                self.errors += 1;
+                Ok(StepResult::Continue)
+            },
+            result => result
+        }
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -657,47 +713,65 @@ impl<'a, R, I, OF, E> Reducing<I, OF, E> for ReplaceReducer<R, I>
     }
 }
 
-pub fn replace<T>(replacements: HashMap<T, T>) -> ReplaceTransducer<T> {
-    ReplaceTransducer(replacements)
+/// Swallows up to `max` errors from the reducing function it is applied
+/// to, the next is passed on as usual
+pub fn limit_errors(max: usize) -> LimitErrorsTransducer {
+    LimitErrorsTransducer(max)
 }
 
-pub struct PartitionByTransducer<F, T, R>
-    where F: Fn(&T) -> R {
+/// Receives the errors swallowed by `limit_errors_collect`.  As with
+/// `ErrorCount` it can be shared across threads, so the pipeline stays `Send`
+#[cfg(feature = "std")]
+pub struct CollectedErrors<E>(Arc<Mutex<Vec<E>>>);
 
-    f: F,
-    t: PhantomData<T>
+#[cfg(feature = "std")]
+impl<E> Clone for CollectedErrors<E> {
+    fn clone(&self) -> Self {
+        CollectedErrors(self.0.clone())
+    }
 }
 
-pub struct PartitionByReducer<RF, F, T, R>
-    where F: Fn(&T) -> R {
+#[cfg(feature = "std")]
+impl<E> CollectedErrors<E> {
+    pub fn take(&self) -> Vec<E> {
+        mem::take(&mut *self.0.lock().unwrap())
+    }
+}
 
-    rf: RF,
-    t: PartitionByTransducer<F, T, R>,
-    holder: Vec<T>,
-    last_res: Option<R>
+#[cfg(feature = "std")]
+#[must_use = "transducers do nothing unless applied"]
+pub struct LimitErrorsCollectTransducer<E> {
+    max: usize,
+    errors: CollectedErrors<E>
 }
 
-impl<RI, F, T, R> Transducer<RI> for PartitionByTransducer<F, T, R>
-    where F: Fn(&T) -> R {
+#[cfg(feature = "std")]
+pub struct LimitErrorsCollectReducer<R, E> {
+    rf: R,
+    max: usize,
+    count: usize,
+    errors: CollectedErrors<E>
+}
 
-    type RO = PartitionByReducer<RI, F, T, R>;
+#[cfg(feature = "std")]
+impl<RI, E> Transducer<RI> for LimitErrorsCollectTransducer<E> {
+    type RO = LimitErrorsCollectReducer<RI, E>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        PartitionByReducer {
+        LimitErrorsCollectReducer {
             rf: reducing_fn,
-            t: self,
-            holder: Vec::new(),
-            last_res: None
+            max: self.max,
+            count: 0,
+            errors: self.errors
         }
     }
 }
 
-impl<R, I, OF, E, F, X> Reducing<I, OF, E> for PartitionByReducer<R, F, I, X>
-    where R: Reducing<Vec<I>, OF, E>,
-          F: Fn(&I) -> X,
-          X: Eq {
+#[cfg(feature = "std")]
+impl<R, I, OF, E> Reducing<I, OF, E> for LimitErrorsCollectReducer<R, E>
+    where R: Reducing<I, OF, E> {
 
-    type Item = Vec<I>;
+    type Item = I;
 
     fn init(&mut self) {
         self.rf.init();
@@ -705,73 +779,61 @@ impl<R, I, OF, E, F, X> Reducing<I, OF, E> for PartitionByReducer<R, F, I, X>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        let last_res = self.last_res.take();
-        match last_res {
-            None => {
-                self.last_res = Some((self.t.f)(&value));
-                self.holder.push(value);
+        match self.rf.step(value) {
+            Err(e) if self.count < self.max => {
+                self.count += 1;
+                self.errors.0.lock().unwrap().push(e);
                 Ok(StepResult::Continue)
             },
-            Some(ref res) => {
-                let new_res = (self.t.f)(&value);
-                if res == &new_res {
-                    self.holder.push(value);
-                    Ok(StepResult::Continue)
-                } else {
-                    self.last_res = Some(new_res);
-                    let mut other_holder = Vec::new();
-                    mem::swap(&mut other_holder, &mut self.holder);
-                    self.holder.push(value);
-                    self.rf.step(other_holder)
-                }
-            }
+            result => result
         }
     }
 
     fn complete(&mut self) -> Result<(), E> {
-        if self.holder.len() > 0 {
-            let mut other_holder = Vec::new();
-            mem::swap(&mut other_holder, &mut self.holder);
-            try!(self.rf.step(other_holder));
-        }
         self.rf.complete()
     }
 }
 
-pub fn partition_by<F, T, R>(partition_func: F) -> PartitionByTransducer<F, T, R>
-    where F: Fn(&T) -> R {
-
-    PartitionByTransducer {
-        f: partition_func,
-        t: PhantomData
-    }
+/// As `limit_errors`, but the swallowed errors are kept in the returned
+/// `CollectedErrors`.  Requires the `std` feature
+#[cfg(feature = "std")]
+pub fn limit_errors_collect<E>(max: usize) -> (LimitErrorsCollectTransducer<E>, CollectedErrors<E>) {
+    let errors = CollectedErrors(Arc::new(Mutex::new(Vec::new())));
+    let transducer = LimitErrorsCollectTransducer {
+        max,
+        errors: errors.clone()
+    };
+    (transducer, errors)
 }
 
-pub struct InterposeTransducer<T>(T);
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapIndexedTransducer<F> {
+    f: F
+}
 
-pub struct InterposeReducer<R, T> {
-    first: bool,
+pub struct MapIndexedReducer<R, F> {
     rf: R,
-    t: InterposeTransducer<T>
+    t: MapIndexedTransducer<F>,
+    count: usize
 }
 
-impl<RI, T> Transducer<RI> for InterposeTransducer<T> {
-    type RO = InterposeReducer<RI, T>;
+impl<F, RI> Transducer<RI> for MapIndexedTransducer<F> {
+    type RO = MapIndexedReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        InterposeReducer {
-            first: true,
+        MapIndexedReducer {
             rf: reducing_fn,
-            t: self
+            t: self,
+            count: 0
         }
     }
 }
 
-impl<R, I, OF, E> Reducing<I, OF, E> for InterposeReducer<R, I>
-    where I: Clone,
-          R: Reducing<I, OF, E> {
+impl<R, F, I, O, OF, E> Reducing<I, OF, E> for MapIndexedReducer<R, F>
+    where F: Fn(usize, I) -> O,
+          R: Reducing<O, OF, E> {
 
-    type Item = I;
+    type Item = O;
 
     fn init(&mut self) {
         self.rf.init();
@@ -779,15 +841,9 @@ impl<R, I, OF, E> Reducing<I, OF, E> for InterposeReducer<R, I>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if self.first {
-            self.first = false;
-        } else {
-            match try!(self.rf.step(self.t.0.clone())) {
-                StepResult::Continue => (),
-                StepResult::Stop => return Ok(StepResult::Stop)
-            }
-        }
-        self.rf.step(value)
+        let idx = self.count;
+        self.count += 1;
+        self.rf.step((self.t.f)(idx, value))
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -795,33 +851,41 @@ impl<R, I, OF, E> Reducing<I, OF, E> for InterposeReducer<R, I>
     }
 }
 
-pub fn interpose<T>(separator: T) -> InterposeTransducer<T> {
-    InterposeTransducer(separator)
+pub fn map_indexed<F, I, O>(f: F) -> MapIndexedTransducer<F>
+    where F: Fn(usize, I) -> O {
+
+    MapIndexedTransducer {
+        f
+    }
 }
 
-pub struct DedupeTransducer<T>(PhantomData<T>);
+#[must_use = "transducers do nothing unless applied"]
+pub struct MapcatTransducer<F> {
+    f: F
+}
 
-pub struct DedupeReducer<R, T> {
-    last_val: Option<T>,
-    rf: R
+pub struct MapcatReducer<R, F> {
+    rf: R,
+    t: MapcatTransducer<F>
 }
 
-impl<RI, T> Transducer<RI> for DedupeTransducer<T> {
-    type RO = DedupeReducer<RI, T>;
+impl<F, RI> Transducer<RI> for MapcatTransducer<F> {
+    type RO = MapcatReducer<RI, F>;
 
     fn new(self, reducing_fn: RI) -> Self::RO {
-        DedupeReducer {
-            last_val: None,
-            rf: reducing_fn
+        MapcatReducer {
+            rf: reducing_fn,
+            t: self
         }
     }
 }
 
-impl<R, I, OF, E> Reducing<I, OF, E> for DedupeReducer<R, I>
-    where I: Eq + Clone,
-          R: Reducing<I, OF, E> {
+impl<R, F, I, O, IO, OF, E> Reducing<I, OF, E> for MapcatReducer<R, F>
+    where IO: IntoIterator<Item=O>,
+          F: Fn(I) -> IO,
+          R: Reducing<O, OF, E> {
 
-    type Item = I;
+    type Item = O;
 
     fn init(&mut self) {
         self.rf.init();
@@ -829,15 +893,14 @@ impl<R, I, OF, E> Reducing<I, OF, E> for DedupeReducer<R, I>
 
     #[inline]
     fn step(&mut self, value: I) -> Result<StepResult, E> {
-        if self.last_val.is_none() {
-            self.last_val = Some(value.clone());
-            self.rf.step(value)
-        } else if self.last_val.as_ref().unwrap() == &value {
-            Ok(StepResult::Continue)
-        } else {
-            self.last_val = Some(value.clone());
-            self.rf.step(value)
+        for o in (self.t.f)(value) {
+            match self.rf.step(o) {
+                Ok(StepResult::Continue) => (),
+                Ok(StepResult::Stop) => return Ok(StepResult::Stop),
+                Err(e) => return Err(e)
+            }
         }
+        Ok(StepResult::Continue)
     }
 
     fn complete(&mut self) -> Result<(), E> {
@@ -845,6 +908,3202 @@ impl<R, I, OF, E> Reducing<I, OF, E> for DedupeReducer<R, I>
     }
 }
 
-pub fn dedupe<T>() -> DedupeTransducer<T> {
-    DedupeTransducer(PhantomData)
+pub fn mapcat<F, I, O, IO>(f: F) -> MapcatTransducer<F>
+    where IO: IntoIterator<Item=O>,
+          F: Fn(I) -> IO {
+
+    MapcatTransducer {
+        f
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct FlatScanTransducer<A, F, C> {
+    acc: Option<A>,
+    f: F,
+    complete: Option<C>
+}
+
+pub struct FlatScanReducer<R, A, F, C> {
+    rf: R,
+    t: FlatScanTransducer<A, F, C>
+}
+
+impl<A, F, C, RI> Transducer<RI> for FlatScanTransducer<A, F, C> {
+    type RO = FlatScanReducer<RI, A, F, C>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        FlatScanReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, A, F, C, I, O, IO, OF, E> Reducing<I, OF, E> for FlatScanReducer<R, A, F, C>
+    where IO: IntoIterator<Item=O>,
+          F: FnMut(&mut A, I) -> IO,
+          C: FnOnce(A) -> IO,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        for o in (self.t.f)(self.t.acc.as_mut().unwrap(), value) {
+            match self.rf.step(o) {
+                Ok(StepResult::Continue) => (),
+                Ok(StepResult::Stop) => return Ok(StepResult::Stop),
+                Err(e) => return Err(e)
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if let (Some(complete), Some(acc)) = (self.t.complete.take(), self.t.acc.take()) {
+            for o in complete(acc) {
+                if let StepResult::Stop = self.rf.step(o)? {
+                    break;
+                }
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+/// As `mapcat`, but the function is also given mutable access to an
+/// accumulator, starting as `init`, carried from one value to the next
+pub fn flat_scan<F, A, I, O, IO>(init: A, f: F) -> FlatScanTransducer<A, F, fn(A) -> IO>
+    where IO: IntoIterator<Item=O>,
+          F: FnMut(&mut A, I) -> IO {
+
+    FlatScanTransducer {
+        acc: Some(init),
+        f,
+        complete: None
+    }
+}
+
+/// The same as `flat_scan`, named after Clojure's general stateful
+/// transducer
+pub fn stateful<S, F, I, O, IO>(init: S, f: F) -> FlatScanTransducer<S, F, fn(S) -> IO>
+    where IO: IntoIterator<Item=O>,
+          F: FnMut(&mut S, I) -> IO {
+
+    flat_scan(init, f)
+}
+
+/// As `stateful`, but on completion the final state is given to
+/// `on_complete`, and everything it returns is passed on
+pub fn stateful_with_complete<S, F, C, I, O, IO>(init: S, f: F, on_complete: C) -> FlatScanTransducer<S, F, C>
+    where IO: IntoIterator<Item=O>,
+          F: FnMut(&mut S, I) -> IO,
+          C: FnOnce(S) -> IO {
+
+    FlatScanTransducer {
+        acc: Some(init),
+        f,
+        complete: Some(on_complete)
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct UnfoldTransducer<F, S> {
+    seed: Option<S>,
+    f: F
+}
+
+pub struct UnfoldReducer<R, F, S> {
+    rf: R,
+    t: UnfoldTransducer<F, S>
+}
+
+impl<F, S, RI> Transducer<RI> for UnfoldTransducer<F, S> {
+    type RO = UnfoldReducer<RI, F, S>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        UnfoldReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, F, S, I, O, OF, E> Reducing<I, OF, E> for UnfoldReducer<R, F, S>
+    where F: Fn(S) -> Option<(O, S)>,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, _: I) -> Result<StepResult, E> {
+        while let Some(seed) = self.t.seed.take() {
+            let (value, next) = match (self.t.f)(seed) {
+                Some(generated) => generated,
+                None => break
+            };
+            self.t.seed = Some(next);
+            match self.rf.step(value)? {
+                StepResult::Stop => return Ok(StepResult::Stop),
+                StepResult::Continue => ()
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Ignores the values themselves, instead each one drives `f`, starting
+/// from `seed`, emitting what it generates until it returns `None` or the
+/// reducing function stops, so an endless generator can be cut short by a
+/// downstream `take`.  The generator's state is kept across values, so
+/// once exhausted nothing more is emitted
+pub fn unfold<F, S, O>(seed: S, f: F) -> UnfoldTransducer<F, S>
+    where F: Fn(S) -> Option<(O, S)> {
+
+    UnfoldTransducer {
+        seed: Some(seed),
+        f
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct FilterTransducer<F> {
+    f: F,
+    inclusive: bool
+}
+
+pub struct FilterReducer<R, F> {
+    rf: R,
+    t: FilterTransducer<F>
+}
+
+impl<F, RI> Transducer<RI> for FilterTransducer<F> {
+    type RO = FilterReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        FilterReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, F, I, OF, E> Reducing<I, OF, E> for FilterReducer<R, F>
+    where F: Fn(&I) -> bool,
+          R: Reducing<I, OF, E> {
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let mut include = (self.t.f)(&value);
+        if !self.t.inclusive {
+            include = !include;
+        }
+        if include {
+            self.rf.step(value)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn filter<F, T>(f: F) -> FilterTransducer<F>
+    where F: Fn(&T) -> bool {
+
+    FilterTransducer {
+        f,
+        inclusive: true
+    }
+}
+
+pub fn remove<F, T>(f: F) -> FilterTransducer<F>
+    where F: Fn(&T) -> bool {
+
+    FilterTransducer {
+        f,
+        inclusive: false
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct KeepTransducer<F>(F);
+
+pub struct KeepReducer<R, F> {
+    rf: R,
+    t: KeepTransducer<F>
+}
+
+impl<F, RI> Transducer<RI> for KeepTransducer<F> {
+    type RO = KeepReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        KeepReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepReducer<R, F>
+    where F: Fn(I) -> Option<O>,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        match (self.t.0)(value) {
+            Some(o) => self.rf.step(o),
+            None => Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn keep<F, I, O>(f: F) -> KeepTransducer<F>
+    where F: Fn(I) -> Option<O> {
+
+    KeepTransducer(f)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct KeepIndexedTransducer<F>(F);
+
+pub struct KeepIndexedReducer<R, F> {
+    rf: R,
+    t: KeepIndexedTransducer<F>,
+    count: usize
+}
+
+impl<F, RI> Transducer<RI> for KeepIndexedTransducer<F> {
+    type RO = KeepIndexedReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        KeepIndexedReducer {
+            rf: reducing_fn,
+            t: self,
+            count: 0
+        }
+    }
+}
+
+impl<R, F, I, O, OF, E> Reducing<I, OF, E> for KeepIndexedReducer<R, F>
+    where F: Fn(usize, I) -> Option<O>,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let idx = self.count;
+        self.count += 1;
+
+        match (self.t.0)(idx, value) {
+            Some(o) => self.rf.step(o),
+            None => Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn keep_indexed<F, I, O>(f: F) -> KeepIndexedTransducer<F>
+    where F: Fn(usize, I) -> Option<O> {
+
+    KeepIndexedTransducer(f)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use = "transducers do nothing unless applied"]
+pub struct PartitionTransducer<T> {
+    size: usize,
+    all: bool,
+    t: PhantomData<T>
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionReducer<RF, T> {
+    t: PartitionTransducer<T>,
+    rf: RF,
+    holder: Vec<T>
+}
+
+impl<RI, T> Transducer<RI> for PartitionTransducer<T> {
+    type RO = PartitionReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        let size = self.size;
+        PartitionReducer {
+            t: self,
+            rf: reducing_fn,
+            holder: Vec::with_capacity(size)
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for PartitionReducer<R, I>
+    where R: Reducing<Vec<I>, OF, E> {
+
+    type Item = Vec<I>;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.holder.push(value);
+        if self.holder.len() == self.t.size {
+            let mut other_holder = Vec::with_capacity(self.t.size);
+            mem::swap(&mut other_holder, &mut self.holder);
+            self.rf.step(other_holder)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if self.t.all && !self.holder.is_empty() {
+            let mut other_holder = Vec::new();
+            mem::swap(&mut other_holder, &mut self.holder);
+            self.rf.step(other_holder)?;
+        }
+        self.rf.complete()
+    }
+}
+
+pub fn partition<T>(num: usize) -> PartitionTransducer<T> {
+    PartitionTransducer {
+        size: num,
+        all: false,
+        t: PhantomData
+    }
+}
+
+pub fn partition_all<T>(num: usize) -> PartitionTransducer<T> {
+    PartitionTransducer {
+        size: num,
+        all: true,
+        t: PhantomData
+    }
+}
+
+/// The same as `partition`, named after `slice::chunks_exact`
+pub fn chunks_exact<T>(num: usize) -> PartitionTransducer<T> {
+    partition(num)
+}
+
+/// Receives the values left over by `chunks_exact_with_remainder` once the
+/// transducer completes
+pub struct Remainder<T>(Rc<RefCell<Vec<T>>>);
+
+impl<T> Remainder<T> {
+    pub fn take(&self) -> Vec<T> {
+        mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct ChunksExactTransducer<T> {
+    partition: PartitionTransducer<T>,
+    remainder: Remainder<T>
+}
+
+pub struct ChunksExactReducer<RF, T> {
+    partition: PartitionReducer<RF, T>,
+    remainder: Remainder<T>
+}
+
+impl<RI, T> Transducer<RI> for ChunksExactTransducer<T> {
+    type RO = ChunksExactReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        ChunksExactReducer {
+            partition: self.partition.new(reducing_fn),
+            remainder: self.remainder
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for ChunksExactReducer<R, I>
+    where R: Reducing<Vec<I>, OF, E> {
+
+    type Item = Vec<I>;
+
+    fn init(&mut self) {
+        self.partition.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.partition.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        mem::swap(&mut *self.remainder.0.borrow_mut(), &mut self.partition.holder);
+        self.partition.complete()
+    }
+}
+
+/// As `chunks_exact`, but the incomplete final chunk is made available
+/// through the returned `Remainder` rather than dropped
+pub fn chunks_exact_with_remainder<T>(num: usize) -> (ChunksExactTransducer<T>, Remainder<T>) {
+    let remainder = Rc::new(RefCell::new(Vec::new()));
+    let transducer = ChunksExactTransducer {
+        partition: partition(num),
+        remainder: Remainder(remainder.clone())
+    };
+    (transducer, Remainder(remainder))
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct RechunkTransducer<T>(PartitionTransducer<T>);
+
+pub struct RechunkReducer<RF, T> {
+    partition: PartitionReducer<RF, T>
+}
+
+impl<RI, T> Transducer<RI> for RechunkTransducer<T> {
+    type RO = RechunkReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        RechunkReducer {
+            partition: self.0.new(reducing_fn)
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<Vec<I>, OF, E> for RechunkReducer<R, I>
+    where R: Reducing<Vec<I>, OF, E> {
+
+    type Item = Vec<I>;
+
+    fn init(&mut self) {
+        self.partition.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: Vec<I>) -> Result<StepResult, E> {
+        for v in value {
+            if let StepResult::Stop = self.partition.step(v)? {
+                return Ok(StepResult::Stop);
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.partition.complete()
+    }
+}
+
+/// Re-partitions a stream of variable sized `Vec`s into `Vec`s of `num`
+/// values, any smaller final one is emitted on completion
+pub fn rechunk<T>(num: usize) -> RechunkTransducer<T> {
+    RechunkTransducer(partition_all(num))
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct ChunkOverlapTransducer<T> {
+    size: usize,
+    overlap: usize,
+    t: PhantomData<T>
+}
+
+pub struct ChunkOverlapReducer<RF, T> {
+    rf: RF,
+    t: ChunkOverlapTransducer<T>,
+    holder: Vec<T>
+}
+
+impl<RI, T> Transducer<RI> for ChunkOverlapTransducer<T> {
+    type RO = ChunkOverlapReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        let size = self.size;
+        ChunkOverlapReducer {
+            rf: reducing_fn,
+            t: self,
+            holder: Vec::with_capacity(size)
+        }
+    }
+}
+
+impl<R, T, OF, E> Reducing<T, OF, E> for ChunkOverlapReducer<R, T>
+    where T: Clone,
+          R: Reducing<Vec<T>, OF, E> {
+
+    type Item = Vec<T>;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        self.holder.push(value);
+        if self.holder.len() == self.t.size {
+            let chunk = self.holder.clone();
+            self.holder.drain(..self.t.size - self.t.overlap);
+            self.rf.step(chunk)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Emits chunks of `size` values, each sharing its first `overlap` values
+/// with the end of the previous chunk.  With no overlap this is the same as
+/// `partition`, an overlap of `size - 1` gives a sliding window.
+pub fn chunk_with_overlap<T>(size: usize, overlap: usize) -> ChunkOverlapTransducer<T> {
+    assert!(overlap < size, "chunk_with_overlap requires the overlap to be less than the size");
+    ChunkOverlapTransducer {
+        size,
+        overlap,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct WindowedTransducer<F, T> {
+    size: usize,
+    f: F,
+    t: PhantomData<T>
+}
+
+pub struct WindowedReducer<R, F, T> {
+    rf: R,
+    size: usize,
+    f: F,
+    window: VecDeque<T>
+}
+
+impl<RI, F, T> Transducer<RI> for WindowedTransducer<F, T> {
+    type RO = WindowedReducer<RI, F, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        WindowedReducer {
+            rf: reducing_fn,
+            size: self.size,
+            f: self.f,
+            window: VecDeque::with_capacity(self.size)
+        }
+    }
+}
+
+impl<R, F, T, O, OF, E> Reducing<T, OF, E> for WindowedReducer<R, F, T>
+    where F: Fn(&[T]) -> O,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() == self.size {
+            let aggregate = (self.f)(self.window.make_contiguous());
+            self.rf.step(aggregate)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Applies `f` to each full sliding window of `size` values, emitting the
+/// aggregate, e.g. a moving sum, without allocating a `Vec` per window
+pub fn windowed<F, T, O>(size: usize, f: F) -> WindowedTransducer<F, T>
+    where F: Fn(&[T]) -> O {
+
+    assert!(size > 0, "windowed requires a size of at least one");
+    WindowedTransducer {
+        size,
+        f,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct PartitionIntoNTransducer(usize);
+
+pub struct PartitionIntoNReducer<R> {
+    rf: R,
+    n: usize,
+    count: usize
+}
+
+impl<RI> Transducer<RI> for PartitionIntoNTransducer {
+    type RO = PartitionIntoNReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        PartitionIntoNReducer {
+            rf: reducing_fn,
+            n: self.0,
+            count: 0
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for PartitionIntoNReducer<R>
+    where R: Reducing<(usize, I), OF, E> {
+
+    type Item = (usize, I);
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let idx = self.count;
+        self.count += 1;
+        if self.count == self.n {
+            self.count = 0;
+        }
+        self.rf.step((idx, value))
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Pairs each value with a partition index from `0` to `n - 1`, assigned
+/// round-robin
+pub fn partition_into_n(n: usize) -> PartitionIntoNTransducer {
+    assert!(n > 0, "partition_into_n requires at least one partition");
+    PartitionIntoNTransducer(n)
+}
+
+/// The same as `partition_into_n`, named for distributing the values across
+/// `n` sub-pipelines
+pub fn interleave_n(n: usize) -> PartitionIntoNTransducer {
+    partition_into_n(n)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct PartitionIntoNBufferedTransducer<T> {
+    n: usize,
+    t: PhantomData<T>
+}
+
+pub struct PartitionIntoNBufferedReducer<R, T> {
+    rf: R,
+    partitions: Vec<Vec<T>>,
+    count: usize
+}
+
+impl<RI, T> Transducer<RI> for PartitionIntoNBufferedTransducer<T> {
+    type RO = PartitionIntoNBufferedReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        PartitionIntoNBufferedReducer {
+            rf: reducing_fn,
+            partitions: (0..self.n).map(|_| Vec::new()).collect(),
+            count: 0
+        }
+    }
+}
+
+impl<R, T, OF, E> Reducing<T, OF, E> for PartitionIntoNBufferedReducer<R, T>
+    where R: Reducing<Vec<Vec<T>>, OF, E> {
+
+    type Item = Vec<Vec<T>>;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        self.partitions[self.count].push(value);
+        self.count += 1;
+        if self.count == self.partitions.len() {
+            self.count = 0;
+        }
+        Ok(StepResult::Continue)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        let partitions = mem::take(&mut self.partitions);
+        self.rf.step(partitions)?;
+        self.rf.complete()
+    }
+}
+
+/// As `partition_into_n`, but buffers the values and emits all `n`
+/// partitions together on completion
+pub fn partition_into_n_buffered<T>(n: usize) -> PartitionIntoNBufferedTransducer<T> {
+    assert!(n > 0, "partition_into_n_buffered requires at least one partition");
+    PartitionIntoNBufferedTransducer {
+        n,
+        t: PhantomData
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use = "transducers do nothing unless applied"]
+pub struct TakeTransducer(usize);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TakeReducer<RF> {
+    rf: RF,
+    taken: usize,
+    t: TakeTransducer
+}
+
+impl<RI> Transducer<RI> for TakeTransducer {
+    type RO = TakeReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        TakeReducer {
+            rf: reducing_fn,
+            taken: 0,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for TakeReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.taken < self.t.0 {
+            self.taken += 1;
+            match self.rf.step(value) {
+                Ok(StepResult::Continue) => if self.taken < self.t.0 {
+                    Ok(StepResult::Continue)
+                } else {
+                    Ok(StepResult::Stop)
+                },
+                Ok(StepResult::Stop) => Ok(StepResult::Stop),
+                Err(e) => Err(e)
+            }
+        } else {
+            Ok(StepResult::Stop)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn take(num: usize) -> TakeTransducer {
+    TakeTransducer(num)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct NthTransducer(usize);
+
+pub struct NthReducer<RF> {
+    rf: RF,
+    count: usize,
+    t: NthTransducer
+}
+
+impl<RI> Transducer<RI> for NthTransducer {
+    type RO = NthReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        NthReducer {
+            rf: reducing_fn,
+            count: 0,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for NthReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.count < self.t.0 {
+            self.count += 1;
+            Ok(StepResult::Continue)
+        } else {
+            self.rf.step(value)?;
+            Ok(StepResult::Stop)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Emits only the value at the zero-indexed position `n`, then stops
+pub fn nth(n: usize) -> NthTransducer {
+    NthTransducer(n)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct StepByTransducer {
+    step: usize,
+    first: usize
+}
+
+pub struct StepByReducer<RF> {
+    rf: RF,
+    step: usize,
+    remaining: usize
+}
+
+impl<RI> Transducer<RI> for StepByTransducer {
+    type RO = StepByReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        StepByReducer {
+            rf: reducing_fn,
+            step: self.step,
+            remaining: self.first
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for StepByReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.remaining == 0 {
+            self.remaining = self.step - 1;
+            self.rf.step(value)
+        } else {
+            self.remaining -= 1;
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Emits the values at the zero-indexed positions `0, n, 2n, ...`, as
+/// `Iterator::step_by`
+pub fn step_by(n: usize) -> StepByTransducer {
+    assert!(n > 0, "step_by requires a step of at least one");
+    StepByTransducer {
+        step: n,
+        first: 0
+    }
+}
+
+/// Emits every `n`th value, i.e. those at the zero-indexed positions
+/// `n - 1, 2n - 1, 3n - 1, ...`
+pub fn every_nth(n: usize) -> StepByTransducer {
+    assert!(n > 0, "every_nth requires n of at least one");
+    StepByTransducer {
+        step: n,
+        first: n - 1
+    }
+}
+
+/// Resets the spacing of a `sample_every`, see `SampleReset::reset`
+#[derive(Clone)]
+pub struct SampleReset(Arc<AtomicBool>);
+
+impl SampleReset {
+    /// The next value is emitted however recently the last one was, and
+    /// the spacing is then counted from it
+    pub fn reset(&self) {
+        self.0.store(true, AtomicOrdering::Relaxed);
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct SampleEveryTransducer {
+    gap: usize,
+    reset: SampleReset
+}
+
+pub struct SampleEveryReducer<R> {
+    rf: R,
+    gap: usize,
+    dropped: Option<usize>,
+    reset: SampleReset
+}
+
+impl<RI> Transducer<RI> for SampleEveryTransducer {
+    type RO = SampleEveryReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        SampleEveryReducer {
+            rf: reducing_fn,
+            gap: self.gap,
+            dropped: None,
+            reset: self.reset
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for SampleEveryReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let reset = self.reset.0.swap(false, AtomicOrdering::Relaxed);
+        match self.dropped {
+            Some(dropped) if !reset && dropped < self.gap => {
+                self.dropped = Some(dropped + 1);
+                Ok(StepResult::Continue)
+            },
+            _ => {
+                self.dropped = Some(0);
+                self.rf.step(value)
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Emits the first value, then the first after at least `n` values have
+/// been dropped since the last emitted.  Left alone this is `step_by(n +
+/// 1)`, but the returned `SampleReset` restarts the spacing, e.g. to emit
+/// straight away after a change of regime.
+pub fn sample_every(n: usize) -> (SampleEveryTransducer, SampleReset) {
+    let reset = SampleReset(Arc::new(AtomicBool::new(false)));
+    (SampleEveryTransducer {
+        gap: n,
+        reset: reset.clone()
+    }, reset)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct TakeWhileTransducer<F>(F);
+
+pub struct TakeWhileReducer<RF, F> {
+    rf: RF,
+    t: TakeWhileTransducer<F>
+}
+
+impl<RI, F> Transducer<RI> for TakeWhileTransducer<F> {
+    type RO = TakeWhileReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        TakeWhileReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E, F> Reducing<I, OF, E> for TakeWhileReducer<R, F>
+    where R: Reducing<I, OF, E>,
+          F: Fn(&I) -> bool {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if (self.t.0)(&value) {
+            self.rf.step(value)
+        } else {
+            Ok(StepResult::Stop)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn take_while<F, T>(pred: F) -> TakeWhileTransducer<F>
+    where F: Fn(&T) -> bool {
+
+    TakeWhileTransducer(pred)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct TakeUntilTransducer<F>(F);
+
+pub struct TakeUntilReducer<RF, F> {
+    rf: RF,
+    t: TakeUntilTransducer<F>
+}
+
+impl<RI, F> Transducer<RI> for TakeUntilTransducer<F> {
+    type RO = TakeUntilReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        TakeUntilReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E, F> Reducing<I, OF, E> for TakeUntilReducer<R, F>
+    where R: Reducing<I, OF, E>,
+          F: Fn(&I) -> bool {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let done = (self.t.0)(&value);
+        match self.rf.step(value)? {
+            StepResult::Continue if !done => Ok(StepResult::Continue),
+            _ => Ok(StepResult::Stop)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn take_until<F, T>(pred: F) -> TakeUntilTransducer<F>
+    where F: Fn(&T) -> bool {
+
+    TakeUntilTransducer(pred)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct DropWhileTransducer<F>(F);
+
+pub struct DropWhileReducer<RF, F> {
+    rf: RF,
+    t: DropWhileTransducer<F>,
+    done: bool
+}
+
+impl<RI, F> Transducer<RI> for DropWhileTransducer<F> {
+    type RO = DropWhileReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        DropWhileReducer {
+            rf: reducing_fn,
+            t: self,
+            done: false
+        }
+    }
+}
+
+impl<R, I, OF, E, F> Reducing<I, OF, E> for DropWhileReducer<R, F>
+    where R: Reducing<I, OF, E>,
+          F: Fn(&I) -> bool {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.done {
+            self.rf.step(value)
+        } else {
+            if !(self.t.0)(&value) {
+                self.done = true;
+                self.rf.step(value)
+            } else {
+                Ok(StepResult::Continue)
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn drop_while<F, T>(pred: F) -> DropWhileTransducer<F>
+    where F: Fn(&T) -> bool {
+
+    DropWhileTransducer(pred)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use = "transducers do nothing unless applied"]
+pub struct DropTransducer(usize);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropReducer<RF> {
+    rf: RF,
+    dropped: usize,
+    d: DropTransducer
+}
+
+impl<RI> Transducer<RI> for DropTransducer {
+    type RO = DropReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        DropReducer {
+            rf: reducing_fn,
+            dropped: 0,
+            d: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for DropReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.dropped < self.d.0 {
+            self.dropped += 1;
+            Ok(StepResult::Continue)
+        } else {
+            self.rf.step(value)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn drop(size: usize) -> DropTransducer {
+    DropTransducer(size)
+}
+
+#[cfg(feature = "std")]
+#[must_use = "transducers do nothing unless applied"]
+pub struct ReplaceTransducer<T>(HashMap<T, T>);
+
+#[cfg(feature = "std")]
+pub struct ReplaceReducer<RF, T> {
+    rf: RF,
+    t: ReplaceTransducer<T>
+}
+
+#[cfg(feature = "std")]
+impl<RI, T> Transducer<RI> for ReplaceTransducer<T> {
+    type RO = ReplaceReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        ReplaceReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, I, OF, E> Reducing<I, OF, E> for ReplaceReducer<R, I>
+    where I: Eq + Hash + Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let v:I = match self.t.0.get(&value) {
+            Some(val) => val.clone(),
+            None => value
+        };
+        self.rf.step(v)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn replace<T>(replacements: HashMap<T, T>) -> ReplaceTransducer<T> {
+    ReplaceTransducer(replacements)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct ReplaceOrderedTransducer<T>(BTreeMap<T, T>);
+
+pub struct ReplaceOrderedReducer<RF, T> {
+    rf: RF,
+    t: ReplaceOrderedTransducer<T>
+}
+
+impl<RI, T> Transducer<RI> for ReplaceOrderedTransducer<T> {
+    type RO = ReplaceOrderedReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        ReplaceOrderedReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for ReplaceOrderedReducer<R, I>
+    where I: Ord + Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let v:I = match self.t.0.get(&value) {
+            Some(val) => val.clone(),
+            None => value
+        };
+        self.rf.step(v)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// As `replace`, but with the replacements held in a `BTreeMap`, so keys
+/// must be `Ord` rather than `Hash`.  Available without `std`
+pub fn replace_ordered<T>(replacements: BTreeMap<T, T>) -> ReplaceOrderedTransducer<T> {
+    ReplaceOrderedTransducer(replacements)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct PartitionByTransducer<F, T, R>
+    where F: Fn(&T) -> R {
+
+    f: F,
+    t: PhantomData<T>
+}
+
+pub struct PartitionByReducer<RF, F, T, R>
+    where F: Fn(&T) -> R {
+
+    rf: RF,
+    t: PartitionByTransducer<F, T, R>,
+    holder: Vec<T>,
+    last_res: Option<R>
+}
+
+impl<RI, F, T, R> Transducer<RI> for PartitionByTransducer<F, T, R>
+    where F: Fn(&T) -> R {
+
+    type RO = PartitionByReducer<RI, F, T, R>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        PartitionByReducer {
+            rf: reducing_fn,
+            t: self,
+            holder: Vec::new(),
+            last_res: None
+        }
+    }
+}
+
+impl<R, I, OF, E, F, X> Reducing<I, OF, E> for PartitionByReducer<R, F, I, X>
+    where R: Reducing<Vec<I>, OF, E>,
+          F: Fn(&I) -> X,
+          X: Eq {
+
+    type Item = Vec<I>;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let last_res = self.last_res.take();
+        match last_res {
+            None => {
+                self.last_res = Some((self.t.f)(&value));
+                self.holder.push(value);
+                Ok(StepResult::Continue)
+            },
+            Some(res) => {
+                let new_res = (self.t.f)(&value);
+                if res == new_res {
+                    self.last_res = Some(res);
+                    self.holder.push(value);
+                    Ok(StepResult::Continue)
+                } else {
+                    self.last_res = Some(new_res);
+                    let mut other_holder = Vec::new();
+                    mem::swap(&mut other_holder, &mut self.holder);
+                    self.holder.push(value);
+                    self.rf.step(other_holder)
+                }
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if !self.holder.is_empty() {
+            let mut other_holder = Vec::new();
+            mem::swap(&mut other_holder, &mut self.holder);
+            self.rf.step(other_holder)?;
+        }
+        self.rf.complete()
+    }
+}
+
+pub fn partition_by<F, T, R>(partition_func: F) -> PartitionByTransducer<F, T, R>
+    where F: Fn(&T) -> R {
+
+    PartitionByTransducer {
+        f: partition_func,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct GroupAdjacentByTransducer<F, T> {
+    f: F,
+    t: PhantomData<T>
+}
+
+pub struct GroupAdjacentByReducer<RF, F, T, K> {
+    rf: RF,
+    t: GroupAdjacentByTransducer<F, T>,
+    holder: Vec<T>,
+    key: Option<K>
+}
+
+impl<RI, F, T, K> Transducer<RI> for GroupAdjacentByTransducer<F, T>
+    where F: Fn(&T) -> K {
+
+    type RO = GroupAdjacentByReducer<RI, F, T, K>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        GroupAdjacentByReducer {
+            rf: reducing_fn,
+            t: self,
+            holder: Vec::new(),
+            key: None
+        }
+    }
+}
+
+impl<R, I, OF, E, F, K> Reducing<I, OF, E> for GroupAdjacentByReducer<R, F, I, K>
+    where R: Reducing<(K, Vec<I>), OF, E>,
+          F: Fn(&I) -> K,
+          K: Eq {
+
+    type Item = (K, Vec<I>);
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let key = (self.t.f)(&value);
+        match self.key.take() {
+            Some(last) if last != key => {
+                self.key = Some(key);
+                let group = mem::take(&mut self.holder);
+                self.holder.push(value);
+                self.rf.step((last, group))
+            },
+            _ => {
+                self.key = Some(key);
+                self.holder.push(value);
+                Ok(StepResult::Continue)
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if let Some(key) = self.key.take() {
+            let group = mem::take(&mut self.holder);
+            self.rf.step((key, group))?;
+        }
+        self.rf.complete()
+    }
+}
+
+/// As `partition_by`, but each run is emitted along with the key its values
+/// share
+pub fn group_adjacent_by<F, T, K>(key: F) -> GroupAdjacentByTransducer<F, T>
+    where F: Fn(&T) -> K,
+          K: Eq {
+
+    GroupAdjacentByTransducer {
+        f: key,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct AccumulateUntilTransducer<F, T> {
+    f: F,
+    t: PhantomData<T>
+}
+
+pub struct AccumulateUntilReducer<R, F, T> {
+    rf: R,
+    t: AccumulateUntilTransducer<F, T>,
+    holder: Vec<T>
+}
+
+impl<RI, F, T> Transducer<RI> for AccumulateUntilTransducer<F, T>
+    where F: Fn(&[T], &T) -> bool {
+
+    type RO = AccumulateUntilReducer<RI, F, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        AccumulateUntilReducer {
+            rf: reducing_fn,
+            t: self,
+            holder: Vec::new()
+        }
+    }
+}
+
+impl<R, I, OF, E, F> Reducing<I, OF, E> for AccumulateUntilReducer<R, F, I>
+    where R: Reducing<Vec<I>, OF, E>,
+          F: Fn(&[I], &I) -> bool {
+
+    type Item = Vec<I>;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let flush = (self.t.f)(&self.holder, &value);
+        self.holder.push(value);
+        if flush {
+            let mut other_holder = Vec::new();
+            mem::swap(&mut other_holder, &mut self.holder);
+            self.rf.step(other_holder)
+        } else {
+            Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if !self.holder.is_empty() {
+            let mut other_holder = Vec::new();
+            mem::swap(&mut other_holder, &mut self.holder);
+            self.rf.step(other_holder)?;
+        }
+        self.rf.complete()
+    }
+}
+
+/// Buffers values until the predicate, given the buffer and the next value,
+/// returns true; the buffer is then emitted including that value
+pub fn accumulate_until<F, T>(pred: F) -> AccumulateUntilTransducer<F, T>
+    where F: Fn(&[T], &T) -> bool {
+
+    AccumulateUntilTransducer {
+        f: pred,
+        t: PhantomData
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use = "transducers do nothing unless applied"]
+pub struct InterposeTransducer<T>(T);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterposeReducer<R, T> {
+    first: bool,
+    rf: R,
+    t: InterposeTransducer<T>
+}
+
+impl<RI, T> Transducer<RI> for InterposeTransducer<T> {
+    type RO = InterposeReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        InterposeReducer {
+            first: true,
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for InterposeReducer<R, I>
+    where I: Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.first {
+            self.first = false;
+        } else {
+            match self.rf.step(self.t.0.clone())? {
+                StepResult::Continue => (),
+                StepResult::Stop => return Ok(StepResult::Stop)
+            }
+        }
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn interpose<T>(separator: T) -> InterposeTransducer<T> {
+    InterposeTransducer(separator)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct PrependTransducer<T>(Vec<T>);
+
+pub struct PrependReducer<R, T> {
+    rf: R,
+    items: Option<Vec<T>>
+}
+
+impl<RI, T> Transducer<RI> for PrependTransducer<T> {
+    type RO = PrependReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        PrependReducer {
+            rf: reducing_fn,
+            items: Some(self.0)
+        }
+    }
+}
+
+impl<R, T> PrependReducer<R, T> {
+    fn flush<OF, E>(&mut self) -> Result<StepResult, E>
+        where R: Reducing<T, OF, E> {
+
+        if let Some(items) = self.items.take() {
+            for item in items {
+                if let StepResult::Stop = self.rf.step(item)? {
+                    return Ok(StepResult::Stop);
+                }
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+}
+
+impl<R, T, OF, E> Reducing<T, OF, E> for PrependReducer<R, T>
+    where R: Reducing<T, OF, E> {
+
+    type Item = T;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        if let StepResult::Stop = self.flush()? {
+            return Ok(StepResult::Stop);
+        }
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.flush()?;
+        self.rf.complete()
+    }
+}
+
+/// Emits `items` ahead of the values, even if there are none
+pub fn prepend<T>(items: Vec<T>) -> PrependTransducer<T> {
+    PrependTransducer(items)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct GenerateNTransducer<F> {
+    n: usize,
+    f: F
+}
+
+pub struct GenerateNReducer<R, F> {
+    rf: R,
+    t: GenerateNTransducer<F>,
+    emitted: bool
+}
+
+impl<RI, F> Transducer<RI> for GenerateNTransducer<F> {
+    type RO = GenerateNReducer<RI, F>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        GenerateNReducer {
+            rf: reducing_fn,
+            t: self,
+            emitted: false
+        }
+    }
+}
+
+impl<R, F> GenerateNReducer<R, F> {
+    fn flush<T, OF, E>(&mut self) -> Result<StepResult, E>
+        where F: Fn(usize) -> T,
+              R: Reducing<T, OF, E> {
+
+        if !self.emitted {
+            self.emitted = true;
+            for i in 0..self.t.n {
+                if let StepResult::Stop = self.rf.step((self.t.f)(i))? {
+                    return Ok(StepResult::Stop);
+                }
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+}
+
+impl<R, F, T, OF, E> Reducing<T, OF, E> for GenerateNReducer<R, F>
+    where F: Fn(usize) -> T,
+          R: Reducing<T, OF, E> {
+
+    type Item = T;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        if let StepResult::Stop = self.flush()? {
+            return Ok(StepResult::Stop);
+        }
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.flush()?;
+        self.rf.complete()
+    }
+}
+
+/// As `prepend`, but the `n` values emitted ahead are `f(0)` to `f(n - 1)`
+pub fn generate_n<F, T>(n: usize, f: F) -> GenerateNTransducer<F>
+    where F: Fn(usize) -> T {
+
+    GenerateNTransducer {
+        n,
+        f
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct AppendTransducer<T>(Vec<T>);
+
+pub struct AppendReducer<R, T> {
+    rf: R,
+    items: Vec<T>,
+    stopped: bool
+}
+
+impl<RI, T> Transducer<RI> for AppendTransducer<T> {
+    type RO = AppendReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        AppendReducer {
+            rf: reducing_fn,
+            items: self.0,
+            stopped: false
+        }
+    }
+}
+
+impl<R, T, OF, E> Reducing<T, OF, E> for AppendReducer<R, T>
+    where R: Reducing<T, OF, E> {
+
+    type Item = T;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: T) -> Result<StepResult, E> {
+        let result = self.rf.step(value)?;
+        if let StepResult::Stop = result {
+            self.stopped = true;
+        }
+        Ok(result)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if !self.stopped {
+            for item in mem::take(&mut self.items) {
+                if let StepResult::Stop = self.rf.step(item)? {
+                    break;
+                }
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+/// Emits `items` after the values, on completion
+pub fn append<T>(items: Vec<T>) -> AppendTransducer<T> {
+    AppendTransducer(items)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use = "transducers do nothing unless applied"]
+pub struct DedupeTransducer<T>(PhantomData<T>);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DedupeReducer<R, T> {
+    last_val: Option<T>,
+    rf: R
+}
+
+impl<RI, T> Transducer<RI> for DedupeTransducer<T> {
+    type RO = DedupeReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        DedupeReducer {
+            last_val: None,
+            rf: reducing_fn
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for DedupeReducer<R, I>
+    where I: Eq + Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if self.last_val.is_none() {
+            self.last_val = Some(value.clone());
+            self.rf.step(value)
+        } else if self.last_val.as_ref().unwrap() == &value {
+            Ok(StepResult::Continue)
+        } else {
+            self.last_val = Some(value.clone());
+            self.rf.step(value)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+pub fn dedupe<T>() -> DedupeTransducer<T> {
+    DedupeTransducer(PhantomData)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct DedupeWithCountTransducer<T>(PhantomData<T>);
+
+pub struct DedupeWithCountReducer<R, T> {
+    rf: R,
+    run: Option<(T, usize)>
+}
+
+impl<RI, T> Transducer<RI> for DedupeWithCountTransducer<T> {
+    type RO = DedupeWithCountReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        DedupeWithCountReducer {
+            rf: reducing_fn,
+            run: None
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for DedupeWithCountReducer<R, I>
+    where I: Eq,
+          R: Reducing<(I, usize), OF, E> {
+
+    type Item = (I, usize);
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        match self.run.take() {
+            Some((first, count)) if first == value => {
+                self.run = Some((first, count + 1));
+                Ok(StepResult::Continue)
+            },
+            Some(run) => {
+                self.run = Some((value, 1));
+                self.rf.step(run)
+            },
+            None => {
+                self.run = Some((value, 1));
+                Ok(StepResult::Continue)
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if let Some(run) = self.run.take() {
+            self.rf.step(run)?;
+        }
+        self.rf.complete()
+    }
+}
+
+/// Emits the first value of each run of equal values along with the length
+/// of the run.  The duplicates are dropped, so values need not be `Clone`
+pub fn dedupe_with_count<T>() -> DedupeWithCountTransducer<T> {
+    DedupeWithCountTransducer(PhantomData)
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct PeakTransducer<T> {
+    ordering: Ordering,
+    t: PhantomData<T>
+}
+
+pub struct PeakReducer<R, T> {
+    rf: R,
+    ordering: Ordering,
+    prev: Option<T>,
+    current: Option<T>
+}
+
+impl<RI, T> Transducer<RI> for PeakTransducer<T> {
+    type RO = PeakReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        PeakReducer {
+            rf: reducing_fn,
+            ordering: self.ordering,
+            prev: None,
+            current: None
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for PeakReducer<R, I>
+    where I: PartialOrd + Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let prev = self.prev.take();
+        let current = self.current.take();
+        let mut result = Ok(StepResult::Continue);
+        if let (Some(ref p), Some(ref c)) = (&prev, &current) {
+            if c.partial_cmp(p) == Some(self.ordering) &&
+                c.partial_cmp(&value) == Some(self.ordering) {
+                result = self.rf.step(c.clone());
+            }
+        }
+        self.prev = current;
+        self.current = Some(value);
+        result
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        // The buffered item has no successor, so it can never be a peak (or
+        // valley); it is discarded rather than judged on one side only.
+        self.rf.complete()
+    }
+}
+
+pub fn peak_detector<T>() -> PeakTransducer<T>
+    where T: PartialOrd {
+
+    PeakTransducer {
+        ordering: Ordering::Greater,
+        t: PhantomData
+    }
+}
+
+pub fn valley_detector<T>() -> PeakTransducer<T>
+    where T: PartialOrd {
+
+    PeakTransducer {
+        ordering: Ordering::Less,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct RunningExtremumTransducer<T> {
+    ordering: Ordering,
+    t: PhantomData<T>
+}
+
+pub struct RunningExtremumReducer<R, T> {
+    rf: R,
+    ordering: Ordering,
+    current: Option<T>
+}
+
+impl<RI, T> Transducer<RI> for RunningExtremumTransducer<T> {
+    type RO = RunningExtremumReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        RunningExtremumReducer {
+            rf: reducing_fn,
+            ordering: self.ordering,
+            current: None
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for RunningExtremumReducer<R, I>
+    where I: Ord + Clone,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let replace = match self.current {
+            Some(ref c) => value.cmp(c) == self.ordering,
+            None => true
+        };
+        if replace {
+            self.current = Some(value);
+        }
+        self.rf.step(self.current.clone().unwrap())
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Emits the smallest value seen so far at each step
+pub fn running_min<T>() -> RunningExtremumTransducer<T>
+    where T: Ord {
+
+    RunningExtremumTransducer {
+        ordering: Ordering::Less,
+        t: PhantomData
+    }
+}
+
+/// Emits the largest value seen so far at each step
+pub fn running_max<T>() -> RunningExtremumTransducer<T>
+    where T: Ord {
+
+    RunningExtremumTransducer {
+        ordering: Ordering::Greater,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct BackfillTransducer<T>(Option<T>);
+
+pub struct BackfillReducer<R, T> {
+    rf: R,
+    last: Option<T>
+}
+
+impl<RI, T> Transducer<RI> for BackfillTransducer<T> {
+    type RO = BackfillReducer<RI, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        BackfillReducer {
+            rf: reducing_fn,
+            last: self.0
+        }
+    }
+}
+
+impl<R, T, OF, E> Reducing<Option<T>, OF, E> for BackfillReducer<R, T>
+    where T: Clone,
+          R: Reducing<T, OF, E> {
+
+    type Item = T;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: Option<T>) -> Result<StepResult, E> {
+        if value.is_some() {
+            self.last = value;
+        }
+        match self.last {
+            Some(ref last) => self.rf.step(last.clone()),
+            None => Ok(StepResult::Continue)
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Unwraps each `Some`, replacing each `None` with the last value seen.
+/// Any `None`s before the first `Some` are dropped.  A forward fill, which
+/// replaces each `None` with the next value seen, needs the whole stream:
+/// reverse the data, apply `backfill`, then reverse the result.
+pub fn backfill<T>() -> BackfillTransducer<T> {
+    BackfillTransducer(None)
+}
+
+/// As `backfill`, but any `None`s before the first `Some` are replaced
+/// with `default`
+pub fn backfill_with_default<T>(default: T) -> BackfillTransducer<T> {
+    BackfillTransducer(Some(default))
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct MergeSortedTransducer<J>(J);
+
+pub struct MergeSortedReducer<R, J>
+    where J: Iterator {
+
+    rf: R,
+    other: Peekable<J>,
+    stopped: bool
+}
+
+impl<RI, J> Transducer<RI> for MergeSortedTransducer<J>
+    where J: Iterator {
+
+    type RO = MergeSortedReducer<RI, J>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        MergeSortedReducer {
+            rf: reducing_fn,
+            other: self.0.peekable(),
+            stopped: false
+        }
+    }
+}
+
+impl<R, J, I, OF, E> Reducing<I, OF, E> for MergeSortedReducer<R, J>
+    where I: Ord,
+          J: Iterator<Item=I>,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        while self.other.peek().is_some_and(|o| *o < value) {
+            let o = self.other.next().unwrap();
+            if let StepResult::Stop = self.rf.step(o)? {
+                self.stopped = true;
+                return Ok(StepResult::Stop);
+            }
+        }
+        let result = self.rf.step(value)?;
+        if let StepResult::Stop = result {
+            self.stopped = true;
+        }
+        Ok(result)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if !self.stopped {
+            for o in self.other.by_ref() {
+                if let StepResult::Stop = self.rf.step(o)? {
+                    break;
+                }
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+/// Merges the values of `other` into the stream, both must already be
+/// sorted.  Equal values are emitted from the stream first, and whatever
+/// remains of `other` is emitted on completion.
+pub fn merge_sorted<T, J>(other: J) -> MergeSortedTransducer<J::IntoIter>
+    where T: Ord,
+          J: IntoIterator<Item=T> {
+
+    MergeSortedTransducer(other.into_iter())
+}
+
+/// The error raised by `zip_equal` when the stream and the zipped iterator
+/// differ in length
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ZipLengthMismatch {
+    /// The number of values in the zipped iterator
+    pub expected: usize,
+    /// The number of values in the stream, at least as many as had been
+    /// seen when the mismatch was found
+    pub got: usize
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct ZipEqualTransducer<J>(J);
+
+pub struct ZipEqualReducer<R, J> {
+    rf: R,
+    other: J,
+    count: usize,
+    stopped: bool
+}
+
+impl<RI, J> Transducer<RI> for ZipEqualTransducer<J> {
+    type RO = ZipEqualReducer<RI, J>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        ZipEqualReducer {
+            rf: reducing_fn,
+            other: self.0,
+            count: 0,
+            stopped: false
+        }
+    }
+}
+
+impl<R, J, I, OF, E> Reducing<I, OF, E> for ZipEqualReducer<R, J>
+    where J: Iterator,
+          E: From<ZipLengthMismatch>,
+          R: Reducing<(I, J::Item), OF, E> {
+
+    type Item = (I, J::Item);
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        match self.other.next() {
+            Some(o) => {
+                self.count += 1;
+                let result = self.rf.step((value, o))?;
+                if let StepResult::Stop = result {
+                    self.stopped = true;
+                }
+                Ok(result)
+            },
+            None => Err(ZipLengthMismatch {
+                expected: self.count,
+                got: self.count + 1
+            }.into())
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        // Stopping early says nothing about the lengths of either side
+        if !self.stopped {
+            let remaining = self.other.by_ref().count();
+            if remaining > 0 {
+                return Err(ZipLengthMismatch {
+                    expected: self.count + remaining,
+                    got: self.count
+                }.into());
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+/// Pairs each value with the next from `other`, failing with a
+/// `ZipLengthMismatch` unless both run out together
+pub fn zip_equal<J>(other: J) -> ZipEqualTransducer<J::IntoIter>
+    where J: IntoIterator {
+
+    ZipEqualTransducer(other.into_iter())
+}
+
+/// The error raised by `bounded_buffer` when the wrapped transducer holds
+/// more than `max` values without emitting anything
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BufferLimitExceeded {
+    pub max: usize
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct BoundedBufferTransducer<T> {
+    max: usize,
+    t: T
+}
+
+pub struct BoundedBufferReducer<R> {
+    rf: R,
+    max: usize,
+    pending: Rc<Cell<usize>>
+}
+
+/// Sits after the transducer wrapped by `bounded_buffer`, noting each time
+/// it emits
+pub struct BufferEmitReducer<R> {
+    rf: R,
+    pending: Rc<Cell<usize>>
+}
+
+impl<RI, T, RO> Transducer<RI> for BoundedBufferTransducer<T>
+    where T: Transducer<BufferEmitReducer<RI>, RO=RO> {
+
+    type RO = BoundedBufferReducer<RO>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        let pending = Rc::new(Cell::new(0));
+        BoundedBufferReducer {
+            rf: self.t.new(BufferEmitReducer {
+                rf: reducing_fn,
+                pending: pending.clone()
+            }),
+            max: self.max,
+            pending
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for BoundedBufferReducer<R>
+    where E: From<BufferLimitExceeded>,
+          R: Reducing<I, OF, E> {
+
+    type Item = R::Item;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let pending = self.pending.get() + 1;
+        if pending > self.max {
+            return Err(BufferLimitExceeded { max: self.max }.into());
+        }
+        self.pending.set(pending);
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for BufferEmitReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = R::Item;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.pending.set(0);
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Guards a buffering transducer, e.g. `partition_all`, failing with
+/// `BufferLimitExceeded` if it takes in more than `max` values without
+/// emitting anything, rather than growing without bound
+pub fn bounded_buffer<T>(max: usize, transducer: T) -> BoundedBufferTransducer<T> {
+    BoundedBufferTransducer {
+        max,
+        t: transducer
+    }
+}
+
+/// The error raised by `validate_schema` for a value failing validation
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ValidationError {
+    pub failures: Vec<String>
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "validation failed: {}", self.failures.join(", "))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ValidationError {}
+
+pub type Validator<I> = Box<dyn Fn(&I) -> Result<(), String>>;
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct ValidateSchemaTransducer<I> {
+    validators: Vec<Validator<I>>,
+    collect_all: bool
+}
+
+pub struct ValidateSchemaReducer<R, I> {
+    rf: R,
+    t: ValidateSchemaTransducer<I>
+}
+
+impl<RI, I> Transducer<RI> for ValidateSchemaTransducer<I> {
+    type RO = ValidateSchemaReducer<RI, I>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        ValidateSchemaReducer {
+            rf: reducing_fn,
+            t: self
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for ValidateSchemaReducer<R, I>
+    where E: From<ValidationError>,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let mut failures = Vec::new();
+        for validator in &self.t.validators {
+            if let Err(failure) = validator(&value) {
+                failures.push(failure);
+                if !self.t.collect_all {
+                    break;
+                }
+            }
+        }
+        if failures.is_empty() {
+            self.rf.step(value)
+        } else {
+            Err(ValidationError { failures }.into())
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Passes on each value accepted by every validator, failing with a
+/// `ValidationError` holding the first rejection otherwise
+pub fn validate_schema<I>(validators: Vec<Validator<I>>) -> ValidateSchemaTransducer<I> {
+    ValidateSchemaTransducer {
+        validators,
+        collect_all: false
+    }
+}
+
+/// As `validate_schema`, but every validator is run so the
+/// `ValidationError` holds all of a value's rejections
+pub fn validate_schema_collect_all_errors<I>(validators: Vec<Validator<I>>)
+                                             -> ValidateSchemaTransducer<I> {
+    ValidateSchemaTransducer {
+        validators,
+        collect_all: true
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct CoalesceTransducer<F, T> {
+    f: F,
+    t: PhantomData<T>
+}
+
+pub struct CoalesceReducer<R, F, T> {
+    rf: R,
+    t: CoalesceTransducer<F, T>,
+    held: Option<T>
+}
+
+impl<RI, F, T> Transducer<RI> for CoalesceTransducer<F, T> {
+    type RO = CoalesceReducer<RI, F, T>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        CoalesceReducer {
+            rf: reducing_fn,
+            t: self,
+            held: None
+        }
+    }
+}
+
+impl<R, I, OF, E, F> Reducing<I, OF, E> for CoalesceReducer<R, F, I>
+    where R: Reducing<I, OF, E>,
+          F: Fn(I, I) -> Result<I, (I, I)> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        match self.held.take() {
+            None => {
+                self.held = Some(value);
+                Ok(StepResult::Continue)
+            },
+            Some(held) => match (self.t.f)(held, value) {
+                Ok(merged) => {
+                    self.held = Some(merged);
+                    Ok(StepResult::Continue)
+                },
+                Err((prev, next)) => {
+                    self.held = Some(next);
+                    self.rf.step(prev)
+                }
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if let Some(held) = self.held.take() {
+            self.rf.step(held)?;
+        }
+        self.rf.complete()
+    }
+}
+
+pub fn coalesce<F, T>(f: F) -> CoalesceTransducer<F, T>
+    where F: Fn(T, T) -> Result<T, (T, T)> {
+
+    CoalesceTransducer {
+        f,
+        t: PhantomData
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct IdentityTransducer;
+
+impl<RI> Transducer<RI> for IdentityTransducer {
+    type RO = RI;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        reducing_fn
+    }
+}
+
+impl<I> Stateless<I> for IdentityTransducer {
+    type Output = I;
+
+    #[inline]
+    fn apply_one(&self, value: I) -> I {
+        value
+    }
+}
+
+pub fn identity() -> IdentityTransducer {
+    IdentityTransducer
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct TraceTransducer(&'static str);
+
+/// Logs each value, and the result of stepping it, at trace level.  Without
+/// the `trace` feature values are passed through without being logged
+pub struct TraceReducer<R> {
+    rf: R,
+    #[cfg_attr(not(feature = "trace"), allow(dead_code))]
+    name: &'static str
+}
+
+impl<RI> Transducer<RI> for TraceTransducer {
+    type RO = TraceReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        TraceReducer {
+            rf: reducing_fn,
+            name: self.0
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<R, I, OF, E> Reducing<I, OF, E> for TraceReducer<R>
+    where I: Debug,
+          R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        if !log_enabled!(Level::Trace) {
+            return self.rf.step(value)
+        }
+        let repr = alloc::format!("{:?}", value);
+        let result = self.rf.step(value);
+        let outcome = match result {
+            Ok(StepResult::Continue) => "continue",
+            Ok(StepResult::Stop) => "stop",
+            Err(_) => "error"
+        };
+        trace!("{}: {} -> {}", self.name, repr, outcome);
+        result
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        trace!("{}: complete", self.name);
+        self.rf.complete()
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+impl<R, I, OF, E> Reducing<I, OF, E> for TraceReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Logs each value passing through under the given name, this requires the
+/// `trace` feature, otherwise values are passed through unchanged
+pub fn trace_transducer(name: &'static str) -> TraceTransducer {
+    TraceTransducer(name)
+}
+
+/// Logs each value reaching `inner` under the given name, this requires the
+/// `trace` feature, otherwise values are passed to `inner` unchanged
+pub fn trace_reducer<R>(name: &'static str, inner: R) -> TraceReducer<R> {
+    TraceReducer {
+        rf: inner,
+        name
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[must_use = "transducers do nothing unless applied"]
+pub struct MetricsTransducer(&'static str);
+
+/// Counts values, errors and completions through the `metrics` crate
+#[cfg(feature = "metrics")]
+pub struct MetricsReducer<R> {
+    rf: R,
+    items: Counter,
+    errors: Counter,
+    completions: Counter
+}
+
+#[cfg(feature = "metrics")]
+impl<RI> Transducer<RI> for MetricsTransducer {
+    type RO = MetricsReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        MetricsReducer {
+            rf: reducing_fn,
+            items: counter!(self.0),
+            errors: counter!(format!("{}.errors", self.0)),
+            completions: counter!(format!("{}.completions", self.0))
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<R, I, OF, E> Reducing<I, OF, E> for MetricsReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.items.increment(1);
+        let result = self.rf.step(value);
+        if result.is_err() {
+            self.errors.increment(1);
+        }
+        result
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        let result = self.rf.complete();
+        match result {
+            Ok(()) => self.completions.increment(1),
+            Err(_) => self.errors.increment(1)
+        }
+        result
+    }
+}
+
+/// Counts the values passing through in the counter `name`, and any errors
+/// and completions in `name.errors` and `name.completions`.  The counters
+/// are registered with the recorder installed when the transducer is
+/// applied.
+#[cfg(feature = "metrics")]
+pub fn metrics(name: &'static str) -> MetricsTransducer {
+    MetricsTransducer(name)
+}
+
+/// The counts of one stage wrapped by `Stats::stage`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageStats {
+    pub name: &'static str,
+    pub received: usize,
+    pub passed: usize
+}
+
+impl StageStats {
+    /// Values received but not passed on, zero for stages that expand
+    /// values, such as `mapcat`
+    pub fn dropped(&self) -> usize {
+        self.received.saturating_sub(self.passed)
+    }
+}
+
+#[derive(Default)]
+struct StatsState {
+    stages: Vec<StageStats>,
+    /// Indices into `stages`, from the last stage values pass through to
+    /// the first
+    order: Vec<usize>
+}
+
+/// Counts the values received and passed on by each stage wrapped with
+/// `stage`.  Only wrapped stages are counted, so instrumentation costs
+/// nothing elsewhere.  A `Stats` is meant for a single application.
+#[derive(Clone, Default)]
+pub struct Stats(Rc<RefCell<StatsState>>);
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats::default()
+    }
+
+    /// Wraps `transducer` so its counts are recorded under `name`
+    pub fn stage<T>(&self, name: &'static str, transducer: T) -> InstrumentedTransducer<T> {
+        InstrumentedTransducer {
+            stats: self.clone(),
+            name,
+            transducer
+        }
+    }
+
+    /// The counts so far, in the order values pass through the stages
+    pub fn snapshot(&self) -> Vec<StageStats> {
+        let state = self.0.borrow();
+        state.order.iter().rev().map(|&idx| state.stages[idx].clone()).collect()
+    }
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct InstrumentedTransducer<T> {
+    stats: Stats,
+    name: &'static str,
+    transducer: T
+}
+
+/// Counts the values entering a stage
+pub struct StageInReducer<R> {
+    rf: R,
+    stats: Stats,
+    idx: usize
+}
+
+/// Counts the values leaving a stage
+pub struct StageOutReducer<R> {
+    rf: R,
+    stats: Stats,
+    idx: usize
+}
+
+impl<RI, T, RO> Transducer<RI> for InstrumentedTransducer<T>
+    where T: Transducer<StageOutReducer<RI>, RO=RO> {
+
+    type RO = StageInReducer<RO>;
+
+    /// Stages are applied from the last to the first, and any stages
+    /// nested inside this one before it, as values reach them after this
+    /// one.  Ordering this stage after those it wraps means `snapshot` can
+    /// reverse the order to give the order values pass through.
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        let idx = {
+            let mut state = self.stats.0.borrow_mut();
+            state.stages.push(StageStats {
+                name: self.name,
+                received: 0,
+                passed: 0
+            });
+            state.stages.len() - 1
+        };
+        let out = StageOutReducer {
+            rf: reducing_fn,
+            stats: self.stats.clone(),
+            idx
+        };
+        let rf = self.transducer.new(out);
+        self.stats.0.borrow_mut().order.push(idx);
+        StageInReducer {
+            rf,
+            stats: self.stats,
+            idx
+        }
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for StageInReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.stats.0.borrow_mut().stages[self.idx].received += 1;
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+impl<R, I, OF, E> Reducing<I, OF, E> for StageOutReducer<R>
+    where R: Reducing<I, OF, E> {
+
+    type Item = I;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.stats.0.borrow_mut().stages[self.idx].passed += 1;
+        self.rf.step(value)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+#[cfg(feature = "std")]
+#[must_use = "transducers do nothing unless applied"]
+pub struct TimestampTransducer;
+
+#[cfg(feature = "std")]
+pub struct TimestampReducer<R> {
+    rf: R
+}
+
+#[cfg(feature = "std")]
+impl<RI> Transducer<RI> for TimestampTransducer {
+    type RO = TimestampReducer<RI>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        TimestampReducer {
+            rf: reducing_fn
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, I, OF, E> Reducing<I, OF, E> for TimestampReducer<R>
+    where R: Reducing<(Instant, I), OF, E> {
+
+    type Item = (Instant, I);
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.rf.step((Instant::now(), value))
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        self.rf.complete()
+    }
+}
+
+/// Pairs each value with the `Instant` at which it was processed
+#[cfg(feature = "std")]
+pub fn timestamp() -> TimestampTransducer {
+    TimestampTransducer
+}
+
+#[must_use = "transducers do nothing unless applied"]
+pub struct StatefulTransducer<N, F, C, O> {
+    init: N,
+    step: F,
+    complete: C,
+    o: PhantomData<O>
+}
+
+pub struct StatefulReducer<R, S, F, C, O> {
+    rf: R,
+    state: Option<S>,
+    step: F,
+    complete: C,
+    buffer: Vec<O>
+}
+
+impl<RI, N, F, C, S, O> Transducer<RI> for StatefulTransducer<N, F, C, O>
+    where N: Fn() -> S {
+
+    type RO = StatefulReducer<RI, S, F, C, O>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        StatefulReducer {
+            rf: reducing_fn,
+            state: Some((self.init)()),
+            step: self.step,
+            complete: self.complete,
+            buffer: Vec::new()
+        }
+    }
+}
+
+impl<R, S, F, C, I, O, OF, E> Reducing<I, OF, E> for StatefulReducer<R, S, F, C, O>
+    where F: Fn(&mut S, I, &mut dyn FnMut(O)) -> Result<StepResult, E>,
+          C: Fn(S, &mut dyn FnMut(O)) -> Result<(), E>,
+          R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        let buffer = &mut self.buffer;
+        let state = self.state.as_mut().unwrap();
+        let result = (self.step)(state, value, &mut |o| buffer.push(o))?;
+        for o in self.buffer.drain(..) {
+            if let StepResult::Stop = self.rf.step(o)? {
+                return Ok(StepResult::Stop);
+            }
+        }
+        Ok(result)
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        if let Some(state) = self.state.take() {
+            let buffer = &mut self.buffer;
+            (self.complete)(state, &mut |o| buffer.push(o))?;
+            for o in self.buffer.drain(..) {
+                if let StepResult::Stop = self.rf.step(o)? {
+                    break;
+                }
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+/// Builds a custom stateful transducer from closures. `init` creates the
+/// state, `step` is given the state, each value and a callback through
+/// which to emit any number of outputs, and `complete` is given the final
+/// state along with the same callback
+pub fn stateful_transducer<N, F, C, S, I, O, E>(init: N, step: F, complete: C)
+                                                -> StatefulTransducer<N, F, C, O>
+    where N: Fn() -> S,
+          F: Fn(&mut S, I, &mut dyn FnMut(O)) -> Result<StepResult, E>,
+          C: Fn(S, &mut dyn FnMut(O)) -> Result<(), E> {
+
+    StatefulTransducer {
+        init,
+        step,
+        complete,
+        o: PhantomData
+    }
+}
+
+#[cfg(feature = "std")]
+#[must_use = "transducers do nothing unless applied"]
+pub struct ConcurrentMapTransducer<F, I> {
+    pool_size: usize,
+    f: Arc<F>,
+    i: PhantomData<I>
+}
+
+#[cfg(feature = "std")]
+pub struct ConcurrentMapReducer<R, I, O> {
+    rf: R,
+    jobs: Option<Sender<(usize, I)>>,
+    results: Receiver<(usize, thread::Result<O>)>,
+    workers: Vec<JoinHandle<()>>,
+    pending: BTreeMap<usize, O>,
+    sent: usize,
+    next: usize,
+    capacity: usize
+}
+
+#[cfg(feature = "std")]
+impl<RI, F, I, O> Transducer<RI> for ConcurrentMapTransducer<F, I>
+    where F: Fn(I) -> O + Send + Sync + 'static,
+          I: Send + 'static,
+          O: Send + 'static {
+
+    type RO = ConcurrentMapReducer<RI, I, O>;
+
+    fn new(self, reducing_fn: RI) -> Self::RO {
+        let (jobs, job_receiver) = mpsc::channel::<(usize, I)>();
+        let (result_sender, results) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let workers = (0..self.pool_size).map(|_| {
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+            let f = self.f.clone();
+            thread::spawn(move || loop {
+                let job = job_receiver.lock().unwrap().recv();
+                match job {
+                    Ok((idx, value)) => {
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| f(value)));
+                        if result_sender.send((idx, result)).is_err() {
+                            break;
+                        }
+                    },
+                    Err(_) => break
+                }
+            })
+        }).collect();
+        ConcurrentMapReducer {
+            rf: reducing_fn,
+            jobs: Some(jobs),
+            results,
+            workers,
+            pending: BTreeMap::new(),
+            sent: 0,
+            next: 0,
+            capacity: self.pool_size * 2
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, I, O> ConcurrentMapReducer<R, I, O> {
+    /// A panic in `f` is resumed on the thread driving the transducer
+    fn receive(&mut self, (idx, result): (usize, thread::Result<O>)) {
+        match result {
+            Ok(value) => {
+                self.pending.insert(idx, value);
+            },
+            Err(payload) => panic::resume_unwind(payload)
+        }
+    }
+
+    /// Steps, in order, every result ready to be passed on
+    fn emit<OF, E>(&mut self) -> Result<StepResult, E>
+        where R: Reducing<O, OF, E> {
+
+        while let Some(value) = self.pending.remove(&self.next) {
+            self.next += 1;
+            if let StepResult::Stop = self.rf.step(value)? {
+                return Ok(StepResult::Stop);
+            }
+        }
+        Ok(StepResult::Continue)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, I, O, OF, E> Reducing<I, OF, E> for ConcurrentMapReducer<R, I, O>
+    where R: Reducing<O, OF, E> {
+
+    type Item = O;
+
+    fn init(&mut self) {
+        self.rf.init();
+    }
+
+    #[inline]
+    fn step(&mut self, value: I) -> Result<StepResult, E> {
+        self.jobs.as_ref().unwrap().send((self.sent, value)).unwrap();
+        self.sent += 1;
+        while let Ok(result) = self.results.try_recv() {
+            self.receive(result);
+        }
+        loop {
+            if let StepResult::Stop = self.emit()? {
+                return Ok(StepResult::Stop);
+            }
+            if self.sent - self.next < self.capacity {
+                return Ok(StepResult::Continue);
+            }
+            let result = self.results.recv().unwrap();
+            self.receive(result);
+        }
+    }
+
+    fn complete(&mut self) -> Result<(), E> {
+        while self.next < self.sent {
+            if let StepResult::Stop = self.emit()? {
+                break;
+            }
+            if self.next < self.sent {
+                let result = self.results.recv().unwrap();
+                self.receive(result);
+            }
+        }
+        self.rf.complete()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, I, O> Drop for ConcurrentMapReducer<R, I, O> {
+    fn drop(&mut self) {
+        self.jobs.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// As `map`, but `f` is run on a pool of `pool_size` threads.  The results
+/// are passed on in the order of the values; a panic in `f` is resumed on
+/// the thread driving the transducer
+#[cfg(feature = "std")]
+pub fn concurrent_map<F, I, O>(pool_size: usize, f: F) -> ConcurrentMapTransducer<F, I>
+    where F: Fn(I) -> O + Send + Sync + 'static,
+          I: Send + 'static,
+          O: Send + 'static {
+
+    assert!(pool_size > 0, "concurrent_map requires at least one thread");
+    ConcurrentMapTransducer {
+        pool_size,
+        f: Arc::new(f),
+        i: PhantomData
+    }
+}
+
+/// As `concurrent_map`, with one thread for each available CPU
+#[cfg(feature = "std")]
+pub fn concurrent_map_default<F, I, O>(f: F) -> ConcurrentMapTransducer<F, I>
+    where F: Fn(I) -> O + Send + Sync + 'static,
+          I: Send + 'static,
+          O: Send + 'static {
+
+    let pool_size = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    concurrent_map(pool_size, f)
 }